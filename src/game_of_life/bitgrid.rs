@@ -0,0 +1,351 @@
+//! A compact grid storing one bit per cell.
+//!
+//! A `Grid<CellState>` spends several bytes on every cell, which adds up on a full screen
+//! board. `BitGrid` packs the cells of each row into `u64` words instead, at the cost of only
+//! supporting two state, B/S rulesets: there is no `decay`, age tracking or custom `Rule`, and
+//! `EdgeMode::Expanding` grids stay bounded. In return a generation is computed a word, so 64
+//! cells, at a time.
+
+use super::{CellState, EdgeMode, Grid, Neighbourhood, Point, Ruleset, MAX_NEIGHBOURS};
+
+/// Number of cells packed into a word
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// `BitGrid` is a Game of Life grid storing one bit per cell
+/// ```
+/// use game_of_life::{bitgrid::BitGrid, Grid, Point};
+/// let blinker = Grid::from_plaintext("...\nOOO\n...").unwrap();
+/// let mut grid = BitGrid::from(&blinker);
+/// grid.update_states();
+/// assert_eq!(grid.get(Point::new(1, 0)), Some(true));
+/// ```
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row, each row starting on a new word
+    stride: usize,
+    /// The cells, bit `x % 64` of word `y * stride + x / 64` being the cell at `x, y`.
+    /// Bits past the width of a row are always zero.
+    words: Vec<u64>,
+    /// The buffer the next generation is computed into
+    next: Vec<u64>,
+    ruleset: Ruleset,
+    edge_mode: EdgeMode,
+    neighbourhood: Neighbourhood,
+}
+
+impl BitGrid {
+    /// Create a new `BitGrid` of a given `width` and `height` with every cell dead,
+    /// evolving by the standard Conway rules
+    pub fn new_empty(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(WORD_BITS);
+        BitGrid {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+            next: Vec::new(),
+            ruleset: Ruleset::conway(),
+            edge_mode: EdgeMode::default(),
+            neighbourhood: Neighbourhood::default(),
+        }
+    }
+
+    /// The number of columns of the grid
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows of the grid
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the `Ruleset` used to evolve the grid
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// Evolve the grid with the given `Ruleset`
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.ruleset = ruleset;
+    }
+
+    /// Sets how neighbours are found at the edges of the grid
+    pub fn set_edge_mode(&mut self, edge_mode: EdgeMode) {
+        self.edge_mode = edge_mode;
+    }
+
+    /// Sets which surrounding cells are neighbours
+    pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+        self.neighbourhood = neighbourhood;
+    }
+
+    fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
+    }
+
+    fn bit(&self, x: usize, y: usize) -> bool {
+        self.words[y * self.stride + x / WORD_BITS] >> (x % WORD_BITS) & 1 == 1
+    }
+
+    /// Returns whether the cell at `p` is alive, or `None` if `p` is outside the grid
+    pub fn get(&self, p: Point) -> Option<bool> {
+        self.contains(p)
+            .then(|| self.bit(p.x as usize, p.y as usize))
+    }
+
+    /// Brings the cell at `p` to life or kills it.
+    /// Returns `false` without changing anything if `p` is outside the grid.
+    pub fn set(&mut self, p: Point, alive: bool) -> bool {
+        if !self.contains(p) {
+            return false;
+        }
+        let (x, y) = (p.x as usize, p.y as usize);
+        let word = &mut self.words[y * self.stride + x / WORD_BITS];
+        let mask = 1 << (x % WORD_BITS);
+        if alive {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+        true
+    }
+
+    /// Population of the grid i.e number of alive cells
+    pub fn population(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The words of row `y`
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    /// The words of the row `dy` rows from row `y`, or `None` past the edge of a bounded grid
+    fn neighbour_row(&self, y: usize, dy: isize) -> Option<&[u64]> {
+        let ny = y as isize + dy;
+        if (0..self.height as isize).contains(&ny) {
+            return Some(self.row(ny as usize));
+        }
+        match self.edge_mode {
+            EdgeMode::Bounded | EdgeMode::Expanding => None,
+            EdgeMode::Toroidal => Some(self.row(ny.rem_euclid(self.height as isize) as usize)),
+        }
+    }
+
+    /// The west and east neighbours of the cells of word `i` of `row`, shifted into place so
+    /// bit `x` of each is the neighbour of the cell at bit `x`
+    fn sides(&self, row: &[u64], i: usize) -> (u64, u64) {
+        let toroidal = self.edge_mode == EdgeMode::Toroidal;
+        let last = self.stride - 1;
+        let west_carry = if i > 0 {
+            row[i - 1] >> (WORD_BITS - 1)
+        } else if toroidal {
+            row[last] >> ((self.width - 1) % WORD_BITS) & 1
+        } else {
+            0
+        };
+        let east_carry = if i < last { row[i + 1] & 1 } else { 0 };
+        let mut east = row[i] >> 1 | east_carry << (WORD_BITS - 1);
+        if i == last && toroidal {
+            east |= (row[0] & 1) << ((self.width - 1) % WORD_BITS);
+        }
+        (row[i] << 1 | west_carry, east)
+    }
+
+    /// Re-generates the state of the cells based on the `Ruleset`, like `Grid::update_states`.
+    ///
+    /// The cells of a word are evolved together: the neighbours of all 64 are added up at once
+    /// into a binary count spread over four words, one per bit.
+    pub fn update_states(&mut self) {
+        let mut next = std::mem::take(&mut self.next);
+        next.clear();
+        next.resize(self.words.len(), 0);
+        let births: [bool; MAX_NEIGHBOURS as usize + 1] =
+            std::array::from_fn(|count| self.ruleset.is_born(count as i32));
+        let survivals: [bool; MAX_NEIGHBOURS as usize + 1] =
+            std::array::from_fn(|count| self.ruleset.survives(count as i32));
+        let diagonals = self.neighbourhood == Neighbourhood::Moore;
+        // Bits past the width of the last word of a row stay zero
+        let last_mask = match self.width % WORD_BITS {
+            0 => u64::MAX,
+            used => (1 << used) - 1,
+        };
+        for y in 0..self.height {
+            let row = self.row(y);
+            let rows_around = [self.neighbour_row(y, -1), self.neighbour_row(y, 1)];
+            for i in 0..self.stride {
+                let mut count = [0; 4];
+                let (west, east) = self.sides(row, i);
+                add_neighbours(&mut count, west);
+                add_neighbours(&mut count, east);
+                for around in rows_around.into_iter().flatten() {
+                    add_neighbours(&mut count, around[i]);
+                    if diagonals {
+                        let (west, east) = self.sides(around, i);
+                        add_neighbours(&mut count, west);
+                        add_neighbours(&mut count, east);
+                    }
+                }
+                let alive = row[i];
+                let mut lives = 0;
+                for (neighbours, (born, survives)) in births.iter().zip(&survivals).enumerate() {
+                    let matching = cells_counting(&count, neighbours);
+                    if *born {
+                        lives |= !alive & matching;
+                    }
+                    if *survives {
+                        lives |= alive & matching;
+                    }
+                }
+                if i == self.stride - 1 {
+                    lives &= last_mask;
+                }
+                next[y * self.stride + i] = lives;
+            }
+        }
+        self.next = std::mem::replace(&mut self.words, next);
+    }
+}
+
+/// The cells whose neighbour count in `count` is `neighbours`, as set bits
+fn cells_counting(count: &[u64; 4], neighbours: usize) -> u64 {
+    let mut matching = u64::MAX;
+    for (bit, plane) in count.iter().enumerate() {
+        matching &= if neighbours >> bit & 1 == 1 {
+            *plane
+        } else {
+            !plane
+        };
+    }
+    matching
+}
+
+/// Adds one to the neighbour count of every cell set in `neighbours`, the count of the cell at
+/// bit `x` being bit `x` of each word of `count`, lowest first
+fn add_neighbours(count: &mut [u64; 4], neighbours: u64) {
+    let mut carry = neighbours;
+    for plane in count {
+        let next_carry = *plane & carry;
+        *plane ^= carry;
+        carry = next_carry;
+    }
+}
+
+/// Packs the alive cells of `grid`, `Dying` cells being dead.
+/// Grids with a custom `Rule` other than a `Ruleset` evolve by the Conway rules instead.
+impl From<&Grid<CellState>> for BitGrid {
+    fn from(grid: &Grid<CellState>) -> Self {
+        let mut bits = BitGrid::new_empty(grid.width, grid.height);
+        bits.ruleset = grid.ruleset().unwrap_or_default();
+        bits.edge_mode = grid.edge_mode();
+        bits.neighbourhood = grid.neighbourhood();
+        for p in grid.alive_points() {
+            bits.set(p, true);
+        }
+        bits
+    }
+}
+
+/// Unpacks the cells into a `Grid` with the default glyphs
+impl From<&BitGrid> for Grid<CellState> {
+    fn from(bits: &BitGrid) -> Self {
+        let mut grid = Grid::new_empty(bits.width, bits.height);
+        if bits.ruleset != Ruleset::conway() {
+            grid.set_ruleset(bits.ruleset);
+        }
+        grid.set_edge_mode(bits.edge_mode);
+        grid.set_neighbourhood(bits.neighbourhood);
+        for y in 0..bits.height {
+            for x in 0..bits.width {
+                if bits.bit(x, y) {
+                    let alive = CellState::Alive(grid.alive_glyph);
+                    grid.set_cell(Point::new(x as i32, y as i32), alive);
+                }
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_set() {
+        let mut bits = BitGrid::new_empty(70, 2);
+        assert!(bits.set(Point::new(65, 1), true));
+        assert!(!bits.set(Point::new(70, 0), true));
+        assert_eq!(bits.get(Point::new(65, 1)), Some(true));
+        assert_eq!(bits.get(Point::new(64, 1)), Some(false));
+        assert_eq!(bits.get(Point::new(-1, 0)), None);
+        assert_eq!(bits.population(), 1);
+        bits.set(Point::new(65, 1), false);
+        assert_eq!(bits.population(), 0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let grid = Grid::new_random(100, 7);
+        let bits = BitGrid::from(&grid);
+        assert_eq!(bits.population(), grid.population());
+        assert_eq!(Grid::from(&bits).cells, grid.cells);
+    }
+
+    #[test]
+    fn test_evolves_like_grid() {
+        let rulesets = [None, Some(Ruleset::parse("B36/S23").unwrap())];
+        for edge_mode in [EdgeMode::Bounded, EdgeMode::Toroidal] {
+            for neighbourhood in [Neighbourhood::Moore, Neighbourhood::VonNeumann] {
+                for ruleset in rulesets {
+                    let mut grid = Grid::new_random(67, 13);
+                    grid.set_edge_mode(edge_mode);
+                    grid.set_neighbourhood(neighbourhood);
+                    if let Some(ruleset) = ruleset {
+                        grid.set_ruleset(ruleset);
+                    }
+                    let mut bits = BitGrid::from(&grid);
+                    for generation in 0..20 {
+                        grid.update_states();
+                        bits.update_states();
+                        assert_eq!(
+                            Grid::from(&bits).cells,
+                            grid.cells,
+                            "{edge_mode:?} {neighbourhood:?} {ruleset:?} generation {generation}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_evolves_like_grid_across_words() {
+        // Rows which fill their last word exactly, spill into another one or are a single cell
+        for width in [1, 2, 63, 64, 65, 128] {
+            for edge_mode in [EdgeMode::Bounded, EdgeMode::Toroidal] {
+                let mut grid = Grid::new_random(width, 9);
+                grid.set_edge_mode(edge_mode);
+                let mut bits = BitGrid::from(&grid);
+                for generation in 0..10 {
+                    grid.update_states();
+                    bits.update_states();
+                    assert_eq!(
+                        Grid::from(&bits).cells,
+                        grid.cells,
+                        "width {width} {edge_mode:?} generation {generation}"
+                    );
+                }
+            }
+        }
+        let mut empty = BitGrid::new_empty(0, 3);
+        empty.update_states();
+        assert_eq!(empty.population(), 0);
+    }
+}
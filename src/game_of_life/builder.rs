@@ -0,0 +1,160 @@
+//! A builder configuring a random grid in one expression.
+//!
+//! ```
+//! use game_of_life::{builder::GridBuilder, EdgeMode, Ruleset};
+//! let grid = GridBuilder::new()
+//!     .width(40)
+//!     .height(20)
+//!     .ruleset(Ruleset::parse("B36/S23").unwrap())
+//!     .edge_mode(EdgeMode::Toroidal)
+//!     .density(0.3)
+//!     .seed(42)
+//!     .build();
+//! ```
+
+use super::{CellState, EdgeMode, Grid, Neighbourhood, Ruleset};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// `GridBuilder` collects the settings of a random `Grid<CellState>`.
+/// The defaults build the same kind of grid as `Grid::new_random(10, 10)`.
+pub struct GridBuilder {
+    width: usize,
+    height: usize,
+    ruleset: Option<Ruleset>,
+    edge_mode: EdgeMode,
+    neighbourhood: Neighbourhood,
+    density: f32,
+    seed: Option<u64>,
+    max_cells: usize,
+}
+
+impl Default for GridBuilder {
+    fn default() -> Self {
+        GridBuilder {
+            width: 10,
+            height: 10,
+            ruleset: None,
+            edge_mode: EdgeMode::default(),
+            neighbourhood: Neighbourhood::default(),
+            density: Grid::DEFAULT_DENSITY,
+            seed: None,
+            max_cells: Grid::MAX_CELLS,
+        }
+    }
+}
+
+impl GridBuilder {
+    /// Starts from the default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of columns of the grid
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The number of rows of the grid
+    pub fn height(mut self, height: usize) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Evolves the grid with `ruleset` rather than the standard Conway rules
+    pub fn ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.ruleset = Some(ruleset);
+        self
+    }
+
+    /// How neighbours are found at the edges of the grid
+    pub fn edge_mode(mut self, edge_mode: EdgeMode) -> Self {
+        self.edge_mode = edge_mode;
+        self
+    }
+
+    /// Which surrounding cells are neighbours
+    pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// The probability of each cell starting alive, clamped to `0.0..=1.0`
+    pub fn density(mut self, density: f32) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Generates the cells from `seed`, so the same settings always build the same grid
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The most cells the grid may hold, `Grid::MAX_CELLS` by default. Larger sizes are scaled
+    /// down to fit, and the cap can't be raised past `Grid::MAX_CELLS`.
+    pub fn max_cells(mut self, max_cells: usize) -> Self {
+        self.max_cells = max_cells;
+        self
+    }
+
+    /// Builds the configured grid
+    pub fn build(self) -> Grid<CellState> {
+        let mut rng = match self.seed {
+            Some(seed) => fastrand::Rng::with_seed(seed),
+            None => fastrand::Rng::new(),
+        };
+        let (width, height) = Grid::capped_size(self.width, self.height, self.max_cells);
+        let mut grid = Grid::new_random_with_rng(width, height, self.density, &mut rng);
+        if let Some(ruleset) = self.ruleset {
+            grid.set_ruleset(ruleset);
+        }
+        grid.set_edge_mode(self.edge_mode);
+        grid.set_neighbourhood(self.neighbourhood);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_fully_specified() {
+        let ruleset = Ruleset::parse("B36/S23").unwrap();
+        let grid = GridBuilder::new()
+            .width(12)
+            .height(7)
+            .ruleset(ruleset)
+            .edge_mode(EdgeMode::Toroidal)
+            .neighbourhood(Neighbourhood::VonNeumann)
+            .density(1.0)
+            .seed(9)
+            .build();
+        assert_eq!((grid.width, grid.height), (12, 7));
+        assert_eq!(grid.ruleset(), Some(ruleset));
+        assert_eq!(grid.edge_mode(), EdgeMode::Toroidal);
+        assert_eq!(grid.neighbourhood(), Neighbourhood::VonNeumann);
+        assert_eq!(grid.population(), 12 * 7);
+    }
+
+    #[test]
+    fn test_defaults_match_new_random() {
+        let grid = GridBuilder::new().seed(5).build();
+        let expected = Grid::new_random_seeded(10, 10, 5);
+        assert_eq!(grid.cells, expected.cells);
+        assert_eq!(grid.ruleset(), None);
+        assert_eq!(grid.edge_mode(), EdgeMode::Bounded);
+        assert_eq!(grid.neighbourhood(), Neighbourhood::Moore);
+    }
+
+    #[test]
+    fn test_max_cells() {
+        let grid = GridBuilder::new()
+            .width(30)
+            .height(20)
+            .max_cells(150)
+            .build();
+        assert_eq!((grid.width, grid.height), (15, 10));
+    }
+}
@@ -0,0 +1,516 @@
+//! A quadtree grid jumping many generations at once with the Hashlife algorithm.
+//!
+//! `HashLifeGrid` stores the board as a tree of square nodes, identical nodes being shared.
+//! The evolution of every node is memoized, so large static or repetitive regions are only
+//! computed once and `step` can advance by thousands of generations in a few calls.
+//! The board is an unbounded plane evolving by a two state, B/S `Ruleset` over the Moore
+//! neighbourhood: there is no `EdgeMode`, `decay` or custom `Rule`.
+
+use std::collections::HashMap;
+
+use super::{CellState, Grid, Point, Ruleset};
+
+/// Index of a node in `HashLifeGrid::nodes`
+type NodeId = u32;
+
+/// The dead leaf
+const DEAD: NodeId = 0;
+/// The alive leaf
+const ALIVE: NodeId = 1;
+
+#[derive(Clone, Copy, Debug)]
+/// A square of `2^level` cells on each side
+struct Node {
+    level: u8,
+    /// The north west, north east, south west and south east quadrants, unused for leaves
+    children: [NodeId; 4],
+    population: u64,
+}
+
+#[derive(Clone, Debug)]
+/// `HashLifeGrid` is a Game of Life board memoizing the evolution of its regions
+/// ```
+/// use game_of_life::{hashlife::HashLifeGrid, patterns, Grid, Point};
+/// let mut life = HashLifeGrid::from(&patterns::glider());
+/// life.step(4);
+/// assert_eq!(life.population(), 5);
+/// assert!(life.get(Point::new(2, 3)));
+/// ```
+pub struct HashLifeGrid {
+    nodes: Vec<Node>,
+    /// Shared branch nodes, by their children
+    branches: HashMap<[NodeId; 4], NodeId>,
+    /// The empty node of each level
+    empty: Vec<NodeId>,
+    /// The center of a node after `2^j` generations, by node and `j`
+    results: HashMap<(NodeId, u8), NodeId>,
+    root: NodeId,
+    /// The position of the top left cell of the root
+    origin: (i64, i64),
+    /// The region converted to and from a `Grid`
+    width: usize,
+    height: usize,
+    ruleset: Ruleset,
+    generation: u64,
+}
+
+impl HashLifeGrid {
+    /// The most generations a board is stepped through. Cells travel at most one cell a
+    /// generation, so up to here the nodes around them stay well inside 64 bit coordinates.
+    pub const MAX_GENERATION: u64 = 1 << 56;
+
+    /// Create an empty board converting to a `Grid` of a given `width` and `height`,
+    /// evolving by the standard Conway rules
+    pub fn new_empty(width: usize, height: usize) -> Self {
+        let leaf = |population| Node {
+            level: 0,
+            children: [DEAD; 4],
+            population,
+        };
+        let mut life = HashLifeGrid {
+            nodes: vec![leaf(0), leaf(1)],
+            branches: HashMap::new(),
+            empty: vec![DEAD],
+            results: HashMap::new(),
+            root: DEAD,
+            origin: (0, 0),
+            width,
+            height,
+            ruleset: Ruleset::conway(),
+            generation: 0,
+        };
+        life.root = life.empty_node(3);
+        life
+    }
+
+    /// Returns the `Ruleset` used to evolve the board
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// Evolve the board with the given `Ruleset`.
+    /// Rulesets giving birth to cells without neighbours would fill the infinite plane, so
+    /// they are refused and `false` is returned.
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) -> bool {
+        if ruleset.is_born(0) {
+            return false;
+        }
+        if ruleset != self.ruleset {
+            self.ruleset = ruleset;
+            self.results.clear();
+        }
+        true
+    }
+
+    /// Number of generations the board has been stepped through
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Population of the whole plane i.e number of alive cells
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    /// Number of distinct nodes stored so far, a rough measure of memory use
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id as usize]
+    }
+
+    fn size(level: u8) -> i64 {
+        1 << level
+    }
+
+    /// Returns the shared node with the given quadrants
+    fn branch(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&id) = self.branches.get(&children) {
+            return id;
+        }
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            level: self.node(children[0]).level + 1,
+            children,
+            population: children.iter().map(|&c| self.node(c).population).sum(),
+        });
+        self.branches.insert(children, id);
+        id
+    }
+
+    fn empty_node(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let below = *self.empty.last().unwrap();
+            let node = self.branch([below; 4]);
+            self.empty.push(node);
+        }
+        self.empty[level as usize]
+    }
+
+    /// Returns whether the cell at `x, y` relative to the top left of `id` is alive
+    fn cell(&self, mut id: NodeId, mut x: i64, mut y: i64) -> bool {
+        loop {
+            let node = self.node(id);
+            if node.population == 0 {
+                return false;
+            }
+            if node.level == 0 {
+                return true;
+            }
+            let half = Self::size(node.level - 1);
+            let quadrant = usize::from(x >= half) + 2 * usize::from(y >= half);
+            id = node.children[quadrant];
+            x %= half;
+            y %= half;
+        }
+    }
+
+    /// Returns `id` with the cell at `x, y` relative to its top left set to `alive`
+    fn with_cell(&mut self, id: NodeId, x: i64, y: i64, alive: bool) -> NodeId {
+        let node = self.node(id);
+        if node.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+        let half = Self::size(node.level - 1);
+        let quadrant = usize::from(x >= half) + 2 * usize::from(y >= half);
+        let mut children = node.children;
+        children[quadrant] = self.with_cell(children[quadrant], x % half, y % half, alive);
+        self.branch(children)
+    }
+
+    /// Returns a node one level up with `id` in its center
+    fn centered(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+        let [nw, ne, sw, se] = node.children;
+        let empty = self.empty_node(node.level - 1);
+        let nw = self.branch([empty, empty, empty, nw]);
+        let ne = self.branch([empty, empty, ne, empty]);
+        let sw = self.branch([empty, sw, empty, empty]);
+        let se = self.branch([se, empty, empty, empty]);
+        self.branch([nw, ne, sw, se])
+    }
+
+    /// Returns the node one level down made of the center of `id`
+    fn center(&mut self, id: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.node(id).children;
+        self.branch([
+            self.node(nw).children[3],
+            self.node(ne).children[2],
+            self.node(sw).children[1],
+            self.node(se).children[0],
+        ])
+    }
+
+    /// Returns whether every alive cell of `id` lies in its center half
+    fn is_padded(&self, id: NodeId) -> bool {
+        let node = self.node(id);
+        let [nw, ne, sw, se] = node.children.map(|c| self.node(c));
+        let center = [
+            nw.children[3],
+            ne.children[2],
+            sw.children[1],
+            se.children[0],
+        ];
+        node.level >= 3
+            && center.iter().map(|&c| self.node(c).population).sum::<u64>() == node.population
+    }
+
+    /// Grows the root around its center
+    fn grow(&mut self) {
+        let half = Self::size(self.node(self.root).level - 1);
+        self.root = self.centered(self.root);
+        self.origin = (self.origin.0 - half, self.origin.1 - half);
+    }
+
+    /// Drops empty borders of the root
+    fn shrink(&mut self) {
+        while self.node(self.root).level > 3 && self.is_padded(self.root) {
+            let quarter = Self::size(self.node(self.root).level - 2);
+            self.root = self.center(self.root);
+            self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        }
+    }
+
+    /// Computes one generation of the 2x2 center of a 4x4 node
+    fn evolve_leaves(&mut self, id: NodeId) -> NodeId {
+        let mut center = [DEAD; 4];
+        for (i, cell) in center.iter_mut().enumerate() {
+            let (x, y) = (1 + i as i64 % 2, 1 + i as i64 / 2);
+            let mut alive = 0;
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if (dx, dy) != (0, 0) && self.cell(id, x + dx, y + dy) {
+                        alive += 1;
+                    }
+                }
+            }
+            let lives = if self.cell(id, x, y) {
+                self.ruleset.survives(alive)
+            } else {
+                self.ruleset.is_born(alive)
+            };
+            if lives {
+                *cell = ALIVE;
+            }
+        }
+        self.branch(center)
+    }
+
+    /// Returns the center of `id` after `2^j` generations, `j` being at most two less than
+    /// the level of `id`
+    fn successor(&mut self, id: NodeId, j: u8) -> NodeId {
+        let node = self.node(id);
+        if node.population == 0 {
+            return self.empty_node(node.level - 1);
+        }
+        if node.level == 2 {
+            return self.evolve_leaves(id);
+        }
+        let j = j.min(node.level - 2);
+        if let Some(&result) = self.results.get(&(id, j)) {
+            return result;
+        }
+        let [nw, ne, sw, se] = node.children.map(|c| self.node(c).children);
+        // The nine overlapping sub squares of half the size of the node, row by row
+        let squares = [
+            node.children[0],
+            self.branch([nw[1], ne[0], nw[3], ne[2]]),
+            node.children[1],
+            self.branch([nw[2], nw[3], sw[0], sw[1]]),
+            self.branch([nw[3], ne[2], sw[1], se[0]]),
+            self.branch([ne[2], ne[3], se[0], se[1]]),
+            node.children[2],
+            self.branch([sw[1], se[0], sw[3], se[2]]),
+            node.children[3],
+        ];
+        let mut c = [DEAD; 9];
+        for (c, square) in c.iter_mut().zip(squares) {
+            *c = self.successor(square, j);
+        }
+        let quadrants = [
+            [c[0], c[1], c[3], c[4]],
+            [c[1], c[2], c[4], c[5]],
+            [c[3], c[4], c[6], c[7]],
+            [c[4], c[5], c[7], c[8]],
+        ];
+        let mut result = [DEAD; 4];
+        for (result, quadrant) in result.iter_mut().zip(quadrants) {
+            let square = self.branch(quadrant);
+            *result = if j < node.level - 2 {
+                // The sub squares already advanced by `2^j` generations
+                self.center(square)
+            } else {
+                self.successor(square, j)
+            };
+        }
+        let result = self.branch(result);
+        self.results.insert((id, j), result);
+        result
+    }
+
+    /// Advances the board by `2^j` generations
+    fn step_power_of_two(&mut self, j: u8) {
+        while self.node(self.root).level < j + 2 || !self.is_padded(self.root) {
+            self.grow();
+        }
+        // Keeps the alive cells a quarter of the root away from its border, further than
+        // they can travel in `2^j` generations
+        self.grow();
+        let level = self.node(self.root).level;
+        self.root = self.successor(self.root, j);
+        let quarter = Self::size(level - 2);
+        self.origin = (self.origin.0 + quarter, self.origin.1 + quarter);
+        self.shrink();
+    }
+
+    /// Advances the board by `generations`, taking one jump per set bit of the count.
+    /// Steps going past `MAX_GENERATION` would overflow the coordinates of the board, so they
+    /// are refused and `false` is returned.
+    pub fn step(&mut self, generations: u64) -> bool {
+        let within_range = self
+            .generation
+            .checked_add(generations)
+            .is_some_and(|generation| generation <= Self::MAX_GENERATION);
+        if !within_range {
+            return false;
+        }
+        for j in 0..u64::BITS as u8 {
+            if generations >> j & 1 == 1 {
+                self.step_power_of_two(j);
+            }
+        }
+        self.generation += generations;
+        true
+    }
+
+    /// Returns whether the cell at `p` is alive, `p` being anywhere on the plane
+    pub fn get(&self, p: Point) -> bool {
+        let (x, y) = (
+            i64::from(p.x) - self.origin.0,
+            i64::from(p.y) - self.origin.1,
+        );
+        let size = Self::size(self.node(self.root).level);
+        (0..size).contains(&x) && (0..size).contains(&y) && self.cell(self.root, x, y)
+    }
+
+    /// Brings the cell at `p` to life or kills it, growing the board as needed
+    pub fn set(&mut self, p: Point, alive: bool) {
+        let (x, y) = (i64::from(p.x), i64::from(p.y));
+        loop {
+            let size = Self::size(self.node(self.root).level);
+            let (dx, dy) = (x - self.origin.0, y - self.origin.1);
+            if (0..size).contains(&dx) && (0..size).contains(&dy) {
+                self.root = self.with_cell(self.root, dx, dy, alive);
+                return;
+            }
+            self.grow();
+        }
+    }
+}
+
+/// Copies the alive cells of `grid`, `Dying` cells being dead.
+/// Grids with a custom `Rule` or a ruleset giving birth without neighbours evolve by the
+/// Conway rules instead, and the edges of the grid no longer stop the cells.
+impl From<&Grid<CellState>> for HashLifeGrid {
+    fn from(grid: &Grid<CellState>) -> Self {
+        let mut life = HashLifeGrid::new_empty(grid.width, grid.height);
+        if let Some(ruleset) = grid.ruleset() {
+            life.set_ruleset(ruleset);
+        }
+        for p in grid.alive_points() {
+            life.set(p, true);
+        }
+        life
+    }
+}
+
+/// Copies the cells inside the `width` and `height` the board was created with into a `Grid`
+/// with the default glyphs, dropping the cells which travelled outside
+impl From<&HashLifeGrid> for Grid<CellState> {
+    fn from(life: &HashLifeGrid) -> Self {
+        let mut grid = Grid::new_empty(life.width, life.height);
+        if life.ruleset != Ruleset::conway() {
+            grid.set_ruleset(life.ruleset);
+        }
+        for y in 0..life.height as i32 {
+            for x in 0..life.width as i32 {
+                let p = Point::new(x, y);
+                if life.get(p) {
+                    let alive = CellState::Alive(grid.alive_glyph);
+                    grid.set_cell(p, alive);
+                }
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns;
+
+    /// Places `pattern` at `offset` on an empty grid of a given `width` and `height`
+    fn board(
+        pattern: &Grid<CellState>,
+        width: usize,
+        height: usize,
+        offset: Point,
+    ) -> Grid<CellState> {
+        let mut grid = Grid::new_empty(width, height);
+        grid.insert(pattern, offset);
+        grid
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut life = HashLifeGrid::new_empty(4, 4);
+        life.set(Point::new(-100, 250), true);
+        life.set(Point::new(3, 3), true);
+        assert!(life.get(Point::new(-100, 250)));
+        assert!(!life.get(Point::new(-99, 250)));
+        assert_eq!(life.population(), 2);
+        life.set(Point::new(3, 3), false);
+        assert_eq!(life.population(), 1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let grid = Grid::new_random(37, 21);
+        let life = HashLifeGrid::from(&grid);
+        assert_eq!(life.population(), grid.population() as u64);
+        assert_eq!(Grid::from(&life).cells, grid.cells);
+    }
+
+    #[test]
+    fn test_glider_evolves_like_grid() {
+        let mut grid = board(&patterns::glider(), 30, 30, Point::new(2, 2));
+        let mut life = HashLifeGrid::from(&grid);
+        for generation in 1..=60 {
+            grid.update_states();
+            life.step(1);
+            assert_eq!(
+                Grid::from(&life).cells,
+                grid.cells,
+                "generation {generation}"
+            );
+        }
+        assert_eq!(life.generation(), 60);
+    }
+
+    #[test]
+    fn test_glider_gun_jumps_like_grid() {
+        let mut grid = board(&patterns::gosper_glider_gun(), 90, 70, Point::new(2, 2));
+        let mut life = HashLifeGrid::from(&grid);
+        for jump in [1, 2, 7, 16, 37] {
+            for _ in 0..jump {
+                grid.update_states();
+            }
+            life.step(jump);
+            assert_eq!(
+                Grid::from(&life).cells,
+                grid.cells,
+                "generation {}",
+                life.generation()
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_jump() {
+        let mut life = HashLifeGrid::from(&patterns::glider());
+        life.step(1 << 20);
+        assert_eq!(life.population(), 5);
+        // A glider travels one cell diagonally every four generations
+        let travelled = 1 << 18;
+        assert!(life.get(Point::new(2 + travelled, 2 + travelled)));
+    }
+
+    #[test]
+    fn test_refuses_steps_past_max_generation() {
+        let mut life = HashLifeGrid::from(&patterns::glider());
+        assert!(!life.step(u64::MAX));
+        assert!(!life.step(1 << 62));
+        assert!(!life.step(HashLifeGrid::MAX_GENERATION + 1));
+        assert_eq!(life.generation(), 0);
+        assert!(life.step(HashLifeGrid::MAX_GENERATION));
+        assert_eq!(life.population(), 5);
+        assert!(!life.step(1));
+        assert_eq!(life.generation(), HashLifeGrid::MAX_GENERATION);
+
+        // A gun keeps sending gliders out to the far edge of the coordinates
+        let mut gun = HashLifeGrid::from(&patterns::gosper_glider_gun());
+        assert!(gun.step(HashLifeGrid::MAX_GENERATION));
+        assert!(gun.population() > 1 << 40);
+    }
+
+    #[test]
+    fn test_refuses_birth_without_neighbours() {
+        let mut life = HashLifeGrid::new_empty(4, 4);
+        assert!(!life.set_ruleset(Ruleset::parse("B0/S8").unwrap()));
+        assert!(life.set_ruleset(Ruleset::parse("B36/S23").unwrap()));
+        assert_eq!(life.ruleset(), Ruleset::parse("B36/S23").unwrap());
+    }
+}
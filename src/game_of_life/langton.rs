@@ -0,0 +1,170 @@
+//! Langton's Ant, a single ant walking over the grid and flipping the cells it leaves.
+//!
+//! See: <https://en.wikipedia.org/wiki/Langton%27s_ant>
+
+use super::{CellState, EdgeMode, Grid, Point, EAST, NORTH, SOUTH, WEST};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `Heading` is the direction an ant is facing, north being up the grid
+pub enum Heading {
+    /// Towards the top row
+    #[default]
+    North,
+    /// Towards the last column
+    East,
+    /// Towards the bottom row
+    South,
+    /// Towards the first column
+    West,
+}
+
+impl Heading {
+    /// The heading after a quarter turn clockwise
+    pub fn right(self) -> Self {
+        match self {
+            Heading::North => Heading::East,
+            Heading::East => Heading::South,
+            Heading::South => Heading::West,
+            Heading::West => Heading::North,
+        }
+    }
+
+    /// The heading after a quarter turn counterclockwise
+    pub fn left(self) -> Self {
+        self.right().right().right()
+    }
+
+    fn direction(self) -> Point {
+        match self {
+            Heading::North => NORTH,
+            Heading::East => EAST,
+            Heading::South => SOUTH,
+            Heading::West => WEST,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// `LangtonAnt` is an ant walking over a grid of dead and alive cells
+/// - On a dead cell the ant turns right, brings the cell to life and moves forward
+/// - On an alive cell the ant turns left, kills the cell and moves forward
+///
+/// The ant wraps around the edges of the grid.
+/// ```
+/// use game_of_life::{langton::LangtonAnt, Point};
+/// let mut ant = LangtonAnt::new(11, 11);
+/// ant.step();
+/// assert_eq!(ant.grid.population(), 1);
+/// assert_eq!(ant.position(), Point::new(6, 5));
+/// ```
+pub struct LangtonAnt {
+    /// The cells the ant walks over
+    pub grid: Grid<CellState>,
+    position: Point,
+    heading: Heading,
+    steps: u64,
+}
+
+impl LangtonAnt {
+    /// Create an ant facing north in the middle of an empty `Grid` of a given `width` and `height`
+    pub fn new(width: usize, height: usize) -> Self {
+        let mut grid = Grid::new_empty(width, height);
+        grid.set_edge_mode(EdgeMode::Toroidal);
+        LangtonAnt {
+            grid,
+            position: Point::new(width as i32 / 2, height as i32 / 2),
+            heading: Heading::default(),
+            steps: 0,
+        }
+    }
+
+    /// The cell the ant is on
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    /// The direction the ant is facing
+    pub fn heading(&self) -> Heading {
+        self.heading
+    }
+
+    /// Number of steps the ant has taken
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Turns the ant, flips the cell it is on and moves it forward one cell.
+    /// Does nothing on an empty grid.
+    pub fn step(&mut self) {
+        let Some(cell) = self.grid.try_get(self.position) else {
+            return;
+        };
+        self.heading = if cell.is_alive() {
+            self.heading.left()
+        } else {
+            self.heading.right()
+        };
+        self.grid.toggle_cell(self.position);
+        let ahead = self.position + self.heading.direction();
+        self.position = Point::new(
+            ahead.x.rem_euclid(self.grid.width as i32),
+            ahead.y.rem_euclid(self.grid.height as i32),
+        );
+        self.steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_steps() {
+        let mut ant = LangtonAnt::new(5, 5);
+        for _ in 0..4 {
+            ant.step();
+        }
+        // Four right turns on dead cells bring the ant back where it started
+        assert_eq!(ant.position(), Point::new(2, 2));
+        assert_eq!(ant.heading(), Heading::North);
+        assert_eq!(ant.grid.population(), 4);
+        ant.step();
+        assert_eq!(ant.heading(), Heading::West);
+        assert_eq!(ant.grid.population(), 3);
+    }
+
+    #[test]
+    fn test_wraps_around_edges() {
+        let mut ant = LangtonAnt::new(1, 1);
+        ant.step();
+        assert_eq!(ant.position(), Point::new(0, 0));
+        assert_eq!(LangtonAnt::new(0, 0).position(), Point::new(0, 0));
+        let mut empty = LangtonAnt::new(0, 0);
+        empty.step();
+        assert_eq!(empty.steps(), 0);
+    }
+
+    #[test]
+    fn test_builds_highway() {
+        // The ant moves chaotically for about 10,000 steps, then repeats a cycle of 104 steps
+        // which moves it two cells diagonally
+        const HIGHWAY_PERIOD: usize = 104;
+        let mut ant = LangtonAnt::new(200, 200);
+        for _ in 0..11_000 {
+            ant.step();
+        }
+        let mut positions = vec![ant.position()];
+        for _ in 0..5 {
+            for _ in 0..HIGHWAY_PERIOD {
+                ant.step();
+            }
+            positions.push(ant.position());
+        }
+        let displacements: Vec<Point> = positions
+            .windows(2)
+            .map(|pair| Point::new(pair[1].x - pair[0].x, pair[1].y - pair[0].y))
+            .collect();
+        assert!(displacements.iter().all(|&d| d == displacements[0]));
+        assert_eq!((displacements[0].x.abs(), displacements[0].y.abs()), (2, 2));
+    }
+}
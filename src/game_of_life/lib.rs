@@ -0,0 +1,3939 @@
+#![warn(missing_docs)]
+//! Game of Life
+//!
+//! Library to manage the grid state for Conways game of life.
+//! Originally based on the [`gridlife`](https://github.com/sinon/game-of-life) crate (MIT).
+//!
+//! See: <https://en.wikipedia.org/wiki/Conway%27s_Game_of_Life>
+//!
+//! ```
+//! use game_of_life::Grid;
+//! let mut grid = Grid::new_random(3, 3);
+//! let mut population = grid.population();
+//! // Run the rules of Game of Life until the population count stabalizes
+//! loop {
+//!     grid.update_states();
+//!     let next_pop = grid.population();
+//!     if next_pop == population {
+//!         break;
+//!     }
+//!     population = next_pop;
+//! }
+//! ```
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{self, Debug, Display},
+    ops::{Add, Index, IndexMut},
+    rc::Rc,
+    str::FromStr,
+};
+
+pub mod bitgrid;
+pub mod builder;
+#[cfg(feature = "hashlife")]
+pub mod hashlife;
+pub mod langton;
+pub mod patterns;
+pub mod plaintext;
+pub mod rle;
+pub mod wireworld;
+
+type Coord = i32;
+
+const NORTH: Point = Point::new(0, -1);
+const NORTH_EAST: Point = Point::new(1, -1);
+const EAST: Point = Point::new(1, 0);
+const SOUTH_EAST: Point = Point::new(1, 1);
+const SOUTH: Point = Point::new(0, 1);
+const SOUTH_WEST: Point = Point::new(-1, 1);
+const WEST: Point = Point::new(-1, 0);
+const NORTH_WEST: Point = Point::new(-1, -1);
+
+const ORTHO_PLUS_DIR: [Point; 8] = [
+    NORTH, NORTH_EAST, EAST, SOUTH_EAST, SOUTH, SOUTH_WEST, WEST, NORTH_WEST,
+];
+
+/// Dead cells added on each side an `EdgeMode::Expanding` grid grows on
+const EXPAND_MARGIN: usize = 8;
+
+const ORTHO_DIR: [Point; 4] = [NORTH, EAST, SOUTH, WEST];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// `Point` is the position of a cell on the `Grid`, with `0, 0` being the top left
+pub struct Point {
+    /// The column of the cell
+    pub x: Coord,
+    /// The row of the cell
+    pub y: Coord,
+}
+impl AsRef<Point> for Point {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Point {
+    /// Create a new `Point` at column `x` and row `y`
+    #[inline]
+    #[must_use]
+    pub const fn new(x: Coord, y: Coord) -> Self {
+        Point { x, y }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+/// `CellState` models whether a cell has an alive or dead population
+pub enum CellState {
+    /// `Alive` with a `char` to be rendered
+    Alive(char),
+    /// `AliveB` with a `char` to be rendered, alive in the second color of two color
+    /// "Immigration" Life, `Alive` being the first
+    AliveB(char),
+    /// `Dead` with a `char` to be rendered
+    Dead(char),
+    /// `Dying` with the number of generations left before the cell is dead, leaving a fading
+    /// trail behind. Only used when the grid has a `decay`, and never counted as alive.
+    Dying(u8),
+}
+
+impl CellState {
+    /// Glyphs used to display `Dying` cells, from nearly dead to recently alive
+    const DYING_GLYPHS: [char; 3] = ['░', '▒', '▓'];
+
+    /// Whether the cell counts as an alive neighbour
+    pub fn is_alive(&self) -> bool {
+        matches!(self, CellState::Alive(_) | CellState::AliveB(_))
+    }
+
+    /// The character the cell is displayed with using `glyphs` rather than its own glyph.
+    /// `Dying` cells are shaded by how recently they were alive.
+    pub fn glyph(&self, glyphs: CellGlyphs) -> char {
+        match self {
+            CellState::Alive(_) | CellState::AliveB(_) => glyphs.alive,
+            CellState::Dead(_) => glyphs.dead,
+            CellState::Dying(level) => Self::dying_glyph(*level),
+        }
+    }
+
+    fn dying_glyph(level: u8) -> char {
+        let shade = usize::from(level).clamp(1, CellState::DYING_GLYPHS.len()) - 1;
+        CellState::DYING_GLYPHS[shade]
+    }
+}
+impl Display for CellState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellState::Dead(c) => {
+                write!(f, "{c}")?;
+            }
+            CellState::Alive(c) | CellState::AliveB(c) => {
+                write!(f, "{c}")?;
+            }
+            CellState::Dying(level) => {
+                write!(f, "{}", CellState::dying_glyph(*level))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `CellGlyphs` are the characters alive and dead cells are displayed with
+pub struct CellGlyphs {
+    /// The glyph of alive cells
+    pub alive: char,
+    /// The glyph of dead cells
+    pub dead: char,
+}
+
+impl Default for CellGlyphs {
+    fn default() -> Self {
+        CellGlyphs {
+            alive: 'X',
+            dead: ' ',
+        }
+    }
+}
+
+/// The largest number of alive neighbours a cell can have
+const MAX_NEIGHBOURS: u32 = 8;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `Ruleset` describes a life-like cellular automaton in B/S notation
+///
+/// `birth` holds the alive neighbour counts that bring a dead cell to life and
+/// `survival` the counts that keep an alive cell alive, e.g. `B3/S23` for Conways game of life.
+/// ```
+/// use game_of_life::Ruleset;
+/// let highlife = Ruleset::parse("B36/S23").unwrap();
+/// assert_eq!(highlife, Ruleset::new(&[3, 6], &[2, 3]).unwrap());
+/// assert_eq!(highlife, Ruleset::highlife());
+/// ```
+pub struct Ruleset {
+    birth: u16,
+    survival: u16,
+}
+
+impl Ruleset {
+    /// Creates a ruleset from the neighbour counts which bring a dead cell to life and those
+    /// which keep an alive cell alive. Each count must be at most 8 and given only once.
+    /// ```
+    /// use game_of_life::{Ruleset, RulesetError};
+    /// assert_eq!(Ruleset::new(&[3], &[2, 3]), Ok(Ruleset::conway()));
+    /// assert_eq!(Ruleset::new(&[3, 3], &[]), Err(RulesetError::DuplicateCount(3)));
+    /// ```
+    pub fn new(birth: &[u8], survival: &[u8]) -> Result<Self, RulesetError> {
+        Ok(Ruleset {
+            birth: Self::counts_mask(birth)?,
+            survival: Self::counts_mask(survival)?,
+        })
+    }
+
+    /// The standard Conways game of life rules, `B3/S23`
+    pub const fn conway() -> Self {
+        Ruleset {
+            birth: 1 << 3,
+            survival: 1 << 2 | 1 << 3,
+        }
+    }
+
+    /// HighLife, `B36/S23`, close to Conway's rules but with a pattern that replicates itself
+    pub const fn highlife() -> Self {
+        Ruleset {
+            birth: 1 << 3 | 1 << 6,
+            survival: 1 << 2 | 1 << 3,
+        }
+    }
+
+    /// Day & Night, `B3678/S34678`, which treats alive cells on dead ones like dead cells on
+    /// alive ones
+    pub const fn day_and_night() -> Self {
+        Ruleset {
+            birth: 1 << 3 | 1 << 6 | 1 << 7 | 1 << 8,
+            survival: 1 << 3 | 1 << 4 | 1 << 6 | 1 << 7 | 1 << 8,
+        }
+    }
+
+    /// Seeds, `B2/S`, where every alive cell dies each generation and most patterns explode
+    pub const fn seeds() -> Self {
+        Ruleset {
+            birth: 1 << 2,
+            survival: 0,
+        }
+    }
+
+    /// Life without Death, `B3/S012345678`, where alive cells never die
+    pub const fn life_without_death() -> Self {
+        Ruleset {
+            birth: 1 << 3,
+            survival: 0b1_1111_1111,
+        }
+    }
+
+    /// Parses a ruleset in B/S notation such as `B3/S23` or `B36/S23`
+    pub fn parse(s: &str) -> Result<Self, RulesetError> {
+        let (birth, survival) = s
+            .trim()
+            .split_once('/')
+            .ok_or(RulesetError::MissingSeparator)?;
+        Ok(Ruleset {
+            birth: Self::counts_mask(&Self::parse_counts(birth, 'B')?)?,
+            survival: Self::counts_mask(&Self::parse_counts(survival, 'S')?)?,
+        })
+    }
+
+    fn parse_counts(s: &str, prefix: char) -> Result<Vec<u8>, RulesetError> {
+        let mut chars = s.chars();
+        match chars.next() {
+            Some(c) if c.eq_ignore_ascii_case(&prefix) => {}
+            _ => return Err(RulesetError::MissingPrefix(prefix)),
+        }
+        chars
+            .map(|c| match c.to_digit(10) {
+                Some(count) => Ok(count as u8),
+                None => Err(RulesetError::InvalidCharacter(c)),
+            })
+            .collect()
+    }
+
+    /// The bitmask with the bit of each of `counts` set, so rules are looked up in one step
+    fn counts_mask(counts: &[u8]) -> Result<u16, RulesetError> {
+        let mut mask: u16 = 0;
+        for &count in counts {
+            if u32::from(count) > MAX_NEIGHBOURS {
+                return Err(RulesetError::CountOutOfRange(count.into()));
+            }
+            if mask & 1 << count != 0 {
+                return Err(RulesetError::DuplicateCount(count.into()));
+            }
+            mask |= 1 << count;
+        }
+        Ok(mask)
+    }
+
+    /// Whether a dead cell with `alive` neighbours is born
+    pub fn is_born(&self, alive: i32) -> bool {
+        (0..=MAX_NEIGHBOURS as i32).contains(&alive) && self.birth & (1 << alive) != 0
+    }
+
+    /// Whether an alive cell with `alive` neighbours survives
+    pub fn survives(&self, alive: i32) -> bool {
+        (0..=MAX_NEIGHBOURS as i32).contains(&alive) && self.survival & (1 << alive) != 0
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+impl FromStr for Ruleset {
+    type Err = RulesetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |counts: u16| -> String {
+            (0..=MAX_NEIGHBOURS)
+                .filter(|n| counts & (1 << n) != 0)
+                .map(|n| n.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+/// `Rule` decides the next state of each cell, see `Grid::set_rule`
+///
+/// The grid takes care of the parts common to all rules: the glyphs of the returned state are
+/// replaced by the grid's own, alive cells that die go through the `decay` and `Dying` cells
+/// count down without consulting the rule.
+/// ```
+/// use game_of_life::{CellState, Grid, Rule};
+/// #[derive(Debug)]
+/// struct Seeds;
+/// impl Rule for Seeds {
+///     fn next(&self, current: CellState, alive_neighbours: u32) -> CellState {
+///         match (current, alive_neighbours) {
+///             (CellState::Dead(_), 2) => CellState::Alive('X'),
+///             _ => CellState::Dead(' '),
+///         }
+///     }
+/// }
+/// let mut grid = Grid::new_random(3, 3);
+/// grid.set_rule(Seeds);
+/// ```
+pub trait Rule: Debug {
+    /// Returns the state of a `current` cell, which is alive or dead, in the next generation
+    fn next(&self, current: CellState, alive_neighbours: u32) -> CellState;
+
+    /// Like `next`, for rules which also care how many neighbours are dead. This is what the
+    /// grid calls, and it calls `next` with the alive count unless overridden.
+    fn next_with_counts(&self, current: CellState, neighbours: NeighbourCounts) -> CellState {
+        self.next(current, neighbours.alive)
+    }
+
+    /// The B/S notation of the rule, `None` unless it is a custom `Ruleset`
+    fn ruleset(&self) -> Option<Ruleset> {
+        None
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `NeighbourCounts` are how many neighbours of a cell are alive and how many dead, `Dying`
+/// neighbours counting as dead
+///
+/// `alive + dead` is the number of neighbours the cell has, which is never more than 8: 8 in the
+/// Moore neighbourhood and 4 in the von Neumann neighbourhood, fewer at the edges of a bounded
+/// grid.
+pub struct NeighbourCounts {
+    /// Neighbours which are alive
+    pub alive: u32,
+    /// Neighbours which are dead or dying
+    pub dead: u32,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `ConwayRule` is the standard Conways game of life rule, and the default of every `Grid`
+/// - Any live cell with 0 or 1 live neighbors becomes dead, because of underpopulation
+/// - Any live cell with 2 or 3 live neighbors stays alive, because its neighborhood is just right
+/// - Any live cell with more than 3 live neighbors becomes dead, because of overpopulation
+/// - Any dead cell with exactly 3 live neighbors becomes alive, by reproduction
+pub struct ConwayRule;
+
+impl Rule for ConwayRule {
+    fn next(&self, current: CellState, alive_neighbours: u32) -> CellState {
+        match (current.is_alive(), alive_neighbours) {
+            (true, 0..=1) => CellState::Dead(' '),
+            (true, 2..=3) => current,
+            (true, _) => CellState::Dead(' '),
+            (false, 3) => CellState::Alive('X'),
+            (false, _) => current,
+        }
+    }
+}
+
+impl Rule for Ruleset {
+    fn next(&self, current: CellState, alive_neighbours: u32) -> CellState {
+        let alive_neighbours = alive_neighbours as i32;
+        match current {
+            _ if current.is_alive() && self.survives(alive_neighbours) => current,
+            _ if current.is_alive() => CellState::Dead(' '),
+            _ if self.is_born(alive_neighbours) => CellState::Alive('X'),
+            _ => current,
+        }
+    }
+
+    fn ruleset(&self) -> Option<Ruleset> {
+        Some(*self)
+    }
+}
+
+/// `Cell` is a state of an arbitrary cellular automaton, evolved by `Grid::evolve`.
+/// Each cell only sees the states of its neighbours, found according to the `EdgeMode` and
+/// `Neighbourhood` of the grid.
+/// ```
+/// use game_of_life::{Cell, Grid, Point};
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Brain {
+///     On,
+///     Dying,
+///     Off,
+/// }
+/// impl Cell for Brain {
+///     fn step(self, neighbours: &[Self]) -> Self {
+///         match self {
+///             Brain::On => Brain::Dying,
+///             Brain::Dying => Brain::Off,
+///             Brain::Off if neighbours.iter().filter(|&&n| n == Brain::On).count() == 2 => Brain::On,
+///             Brain::Off => Brain::Off,
+///         }
+///     }
+/// }
+/// let mut grid = Grid::new_filled(3, 3, Brain::Off);
+/// grid[Point::new(0, 0)] = Brain::On;
+/// grid[Point::new(2, 0)] = Brain::On;
+/// grid.evolve();
+/// assert_eq!(grid[Point::new(1, 1)], Brain::On);
+/// ```
+pub trait Cell: Copy {
+    /// Returns the state of this cell in the next generation
+    fn step(self, neighbours: &[Self]) -> Self;
+}
+
+/// Cells evolve by the standard Conway rules like `ConwayRule`, `Dying` cells counting as dead.
+/// Grids of `CellState` are better evolved by `Grid::update_states`, which also applies their
+/// `Rule`, `decay` and glyphs.
+impl Cell for CellState {
+    fn step(self, neighbours: &[Self]) -> Self {
+        let current = match self {
+            CellState::Dying(_) => CellState::Dead(' '),
+            _ => self,
+        };
+        let alive = neighbours.iter().filter(|n| n.is_alive()).count();
+        ConwayRule.next(current, alive as u32)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `RulesetError` describes why a B/S notation string could not be parsed
+pub enum RulesetError {
+    /// The `/` between the birth and survival counts is missing
+    MissingSeparator,
+    /// The birth (`B`) or survival (`S`) prefix is missing
+    MissingPrefix(char),
+    /// A neighbour count is not a digit
+    InvalidCharacter(char),
+    /// A neighbour count is larger than the number of neighbours a cell has
+    CountOutOfRange(u32),
+    /// A neighbour count is given more than once for births or for survival
+    DuplicateCount(u32),
+}
+
+impl Display for RulesetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesetError::MissingSeparator => {
+                write!(f, "expected birth and survival counts separated by '/'")
+            }
+            RulesetError::MissingPrefix(prefix) => {
+                write!(f, "expected counts to start with '{prefix}'")
+            }
+            RulesetError::InvalidCharacter(c) => write!(f, "invalid neighbour count '{c}'"),
+            RulesetError::CountOutOfRange(count) => write!(
+                f,
+                "neighbour count {count} is out of range, expected 0 to {MAX_NEIGHBOURS}"
+            ),
+            RulesetError::DuplicateCount(count) => {
+                write!(f, "neighbour count {count} is given more than once")
+            }
+        }
+    }
+}
+
+impl Error for RulesetError {}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `EdgeMode` controls how cells on the border of the grid find their neighbours
+pub enum EdgeMode {
+    /// Points outside the grid are not neighbours, so patterns die at the edges
+    #[default]
+    Bounded,
+    /// The left edge neighbours the right edge and the top neighbours the bottom
+    Toroidal,
+    /// Like `Bounded`, but the grid grows before each update on every side alive cells have
+    /// come within one cell of, so patterns can travel on, up to `Grid::MAX_EXPANDED_CELLS`
+    Expanding,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `Neighbourhood` decides which of the surrounding cells count as neighbours
+pub enum Neighbourhood {
+    /// The eight cells around a cell, diagonals included. Conway's rules require this one.
+    #[default]
+    Moore,
+    /// Only the four cells north, east, south and west of a cell
+    VonNeumann,
+}
+
+impl Neighbourhood {
+    fn directions(self) -> &'static [Point] {
+        match self {
+            Neighbourhood::Moore => &ORTHO_PLUS_DIR,
+            Neighbourhood::VonNeumann => &ORTHO_DIR,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `Symmetry` is how a random soup from `Grid::new_random_symmetric` is mirrored
+pub enum Symmetry {
+    /// The right half mirrors the left half
+    Horizontal,
+    /// The bottom half mirrors the top half
+    Vertical,
+    /// Each quarter mirrors its neighbours across both axes
+    #[default]
+    Both,
+    /// The grid looks the same after a quarter turn. Grids which are not square can only look
+    /// the same after a half turn, so they get that instead.
+    Rotational,
+}
+
+impl Symmetry {
+    /// The points `point` is mirrored to in a grid of `width` by `height`, itself included
+    fn images(self, point: Point, width: i32, height: i32) -> [Point; 4] {
+        let Point { x, y } = point;
+        let (mirror_x, mirror_y) = (width - 1 - x, height - 1 - y);
+        match self {
+            Symmetry::Horizontal => [point, Point::new(mirror_x, y), point, point],
+            Symmetry::Vertical => [point, Point::new(x, mirror_y), point, point],
+            Symmetry::Both => [
+                point,
+                Point::new(mirror_x, y),
+                Point::new(x, mirror_y),
+                Point::new(mirror_x, mirror_y),
+            ],
+            Symmetry::Rotational if width == height => [
+                point,
+                Point::new(mirror_y, x),
+                Point::new(mirror_x, mirror_y),
+                Point::new(y, mirror_x),
+            ],
+            Symmetry::Rotational => [point, Point::new(mirror_x, mirror_y), point, point],
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `GridStatus` describes whether the grid has settled, as returned by `Grid::status`
+pub enum GridStatus {
+    /// The grid changed in the last generations, or has not been updated yet
+    Evolving,
+    /// The grid is unchanged from the previous generation
+    Still,
+    /// The grid is back to the state of two generations ago
+    Oscillating,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `SoupResult` summarises how a grid settled, as returned by `Grid::run_until_stable`
+pub struct SoupResult {
+    /// The number of generations stepped
+    pub generations: usize,
+    /// The population after the last generation
+    pub final_population: usize,
+    /// Whether the grid became still or oscillating within the generations stepped
+    pub stabilized: bool,
+}
+
+/// A generation kept by `History`, the cells and their ages if tracked
+type Snapshot = (Vec<CellState>, Option<Vec<u16>>);
+
+#[derive(Clone, Debug, Default)]
+/// A bounded record of past generations, newest last
+struct History {
+    capacity: usize,
+    states: VecDeque<Snapshot>,
+}
+
+impl History {
+    /// Records a generation, dropping the oldest one when full
+    fn push(&mut self, cells: &[CellState], ages: Option<&[u16]>) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Reuse the buffers of the oldest generation rather than allocating new ones
+        let (mut old_cells, mut old_ages) = if self.states.len() >= self.capacity {
+            self.states.pop_front().unwrap_or_default()
+        } else {
+            Default::default()
+        };
+        old_cells.clear();
+        old_cells.extend_from_slice(cells);
+        old_ages = ages.map(|ages| {
+            let mut old_ages = old_ages.take().unwrap_or_default();
+            old_ages.clear();
+            old_ages.extend_from_slice(ages);
+            old_ages
+        });
+        self.states.push_back((old_cells, old_ages));
+    }
+
+    fn clear(&mut self) {
+        self.states.clear();
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct NeighbourState {
+    dead: i32,
+    alive: i32,
+    /// How many of the `alive` neighbours are `AliveB`
+    alive_b: i32,
+}
+
+impl NeighbourState {
+    /// The counts passed to the `Rule` of the grid
+    fn counts(self) -> NeighbourCounts {
+        NeighbourCounts {
+            alive: self.alive as u32,
+            dead: self.dead as u32,
+        }
+    }
+}
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+/// `Grid` holds the state for a Conways game of life
+pub struct Grid<T> {
+    /// The `width` of the grid to be created
+    pub width: usize,
+    /// The `height` of the grid to be created
+    pub height: usize,
+    /// The state of the grid in terms of what cells are alive and dead in automaton
+    pub cells: Vec<T>,
+    /// What character glyph should be used to display a dead population
+    pub dead_glyph: char,
+    /// What character glyph should be used to display an alive population
+    pub alive_glyph: char,
+    /// Cached number of alive cells, see `Grid::population`
+    population: usize,
+    /// The rule used to evolve the grid, shared with clones as rules hold no state
+    rule: Rc<dyn Rule>,
+    /// How neighbours are found at the edges of the grid
+    edge_mode: EdgeMode,
+    /// Which surrounding cells are neighbours
+    neighbourhood: Neighbourhood,
+    /// Where the top left cell is, see `Grid::origin`
+    origin: Point,
+    /// The previous generation, kept to detect still lifes
+    previous: Vec<T>,
+    /// The generation before `previous`, kept to detect period 2 oscillators.
+    /// It is reused as the buffer the next generation is computed into.
+    older: Vec<T>,
+    /// The number of generations a cell spends `Dying` before it is dead
+    decay: u8,
+    /// How many generations each cell has been continuously alive, when age tracking is on
+    ages: Option<Vec<u16>>,
+    /// How much each cell has been alive lately, when heat tracking is on
+    heat: Option<Vec<f32>>,
+    /// The share of its heat a cell keeps every generation
+    heat_decay: f32,
+    /// Whether each cell was born in an odd generation, when birth parity tracking is on
+    birth_parity: Option<Vec<bool>>,
+    /// Whether the current generation is odd, counting from the grid's creation
+    odd_generation: bool,
+    /// Past generations kept for `step_back`
+    history: History,
+}
+
+impl<T> Grid<T> {
+    fn contains(&self, p: &Point) -> bool {
+        p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
+    }
+
+    /// The point of the cell at index `p` of `cells`, the origin on grids without columns
+    fn pos(&self, p: usize) -> Point {
+        let width = self.width.max(1);
+        Point::new((p % width) as i32, (p / width) as i32)
+    }
+    fn idx(&self, p: &Point) -> usize {
+        ((self.width as i32) * p.y + p.x) as usize
+    }
+    /// Like `idx`, but panics if `p` is outside the grid rather than wrapping onto another row
+    fn checked_idx(&self, p: &Point) -> usize {
+        assert!(
+            self.contains(p),
+            "{p:?} is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.idx(p)
+    }
+
+    /// Returns the cell at `p`, or `None` if `p` is outside the grid
+    pub fn try_get<U: AsRef<Point>>(&self, p: U) -> Option<&T> {
+        if self.contains(p.as_ref()) {
+            Some(&self[*p.as_ref()])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cell at `p` mutably, or `None` if `p` is outside the grid.
+    /// Like `IndexMut` this does not keep the `population` up to date.
+    /// ```
+    /// use game_of_life::{CellState, Grid, Point};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// if let Some(cell) = grid.try_get_mut(Point::new(1, 1)) {
+    ///     *cell = CellState::Alive('X');
+    /// }
+    /// assert!(grid.try_get_mut(Point::new(-1, 1)).is_none());
+    /// ```
+    pub fn try_get_mut<U: AsRef<Point>>(&mut self, p: U) -> Option<&mut T> {
+        if self.contains(p.as_ref()) {
+            Some(&mut self[*p.as_ref()])
+        } else {
+            None
+        }
+    }
+
+    /// Returns how neighbours are found at the edges of the grid
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
+    /// Sets how neighbours are found at the edges of the grid
+    pub fn set_edge_mode(&mut self, edge_mode: EdgeMode) {
+        self.edge_mode = edge_mode;
+    }
+
+    /// Returns which surrounding cells are neighbours
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.neighbourhood
+    }
+
+    /// Sets which surrounding cells are neighbours.
+    /// The standard Conway rules assume the default `Neighbourhood::Moore`.
+    /// ```
+    /// use game_of_life::{Grid, Neighbourhood, Point};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// grid.set_neighbourhood(Neighbourhood::VonNeumann);
+    /// assert_eq!(grid.get_neighbours(Point::new(1, 1)).count(), 4);
+    /// ```
+    pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+        self.neighbourhood = neighbourhood;
+    }
+
+    /// Where the top left cell is in the world the grid shows. It moves up and left as an
+    /// `EdgeMode::Expanding` grid grows, so cells keep their place in the world, and is `0, 0`
+    /// otherwise.
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    /// Maps `p` onto the grid according to the `EdgeMode`, `None` if it has no place on the grid
+    fn wrap(&self, p: Point) -> Option<Point> {
+        match self.edge_mode {
+            EdgeMode::Bounded | EdgeMode::Expanding => self.contains(&p).then_some(p),
+            EdgeMode::Toroidal if self.width == 0 || self.height == 0 => None,
+            EdgeMode::Toroidal => Some(Point::new(
+                p.x.rem_euclid(self.width as Coord),
+                p.y.rem_euclid(self.height as Coord),
+            )),
+        }
+    }
+
+    /// Returns the points on the grid surrounding `point`, according to the `EdgeMode`
+    /// and `Neighbourhood`
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let grid = Grid::new_empty(3, 3);
+    /// assert_eq!(grid.get_neighbours(Point::new(0, 0)).count(), 3);
+    /// ```
+    pub fn get_neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbourhood
+            .directions()
+            .iter()
+            .filter_map(move |&d| self.wrap(point + d))
+    }
+
+    /// Builds a grid of the same size and settings whose cells are `f` of the cells of this one.
+    /// Past generations, tracking and the cached `population` are not carried over.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::from_plaintext(".O\nOO").unwrap();
+    /// let bits = grid.map(|cell| u8::from(cell.is_alive()));
+    /// assert_eq!(bits.cells, [0, 1, 1, 1]);
+    /// ```
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            alive_glyph: self.alive_glyph,
+            dead_glyph: self.dead_glyph,
+            population: 0,
+            rule: self.rule.clone(),
+            edge_mode: self.edge_mode,
+            neighbourhood: self.neighbourhood,
+            origin: self.origin,
+            previous: Vec::new(),
+            older: Vec::new(),
+            decay: self.decay,
+            ages: None,
+            heat: None,
+            heat_decay: self.heat_decay,
+            birth_parity: None,
+            odd_generation: false,
+            history: History::default(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Point) -> &Self::Output {
+        &self.cells[self.checked_idx(&pos)]
+    }
+}
+
+/// Mutable access to a cell, panicking if `pos` is outside the grid like indexing a `Vec`.
+///
+/// Unlike `Grid::set_cell` this does not keep the `population` up to date, it is recalculated on
+/// the next `update_states`.
+impl<T> IndexMut<Point> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, pos: Point) -> &mut Self::Output {
+        let idx = self.checked_idx(&pos);
+        &mut self.cells[idx]
+    }
+}
+
+/// Grids are equal when they have the same dimensions and cells, whatever their rule, settings,
+/// glyphs or past generations.
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        !self.differs_from(other)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns whether `other` has different dimensions or cells, checking the dimensions first
+    /// so grids of different sizes are told apart without looking at their cells.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// assert!(grid.differs_from(&Grid::new_empty(3, 4)));
+    /// assert!(!grid.differs_from(&Grid::new_empty(3, 3)));
+    /// grid.toggle_cell(Point::new(1, 1));
+    /// assert!(grid.differs_from(&Grid::new_empty(3, 3)));
+    /// ```
+    pub fn differs_from(&self, other: &Self) -> bool {
+        self.width != other.width || self.height != other.height || self.cells != other.cells
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    /// Create a new `Grid` of a given `width` and `height` with every cell in the `cell` state.
+    /// Sizes holding more than `Grid::MAX_CELLS` are scaled down to fit.
+    /// ```
+    /// use game_of_life::{CellState, Grid};
+    /// let grid = Grid::new_filled(3, 3, CellState::Dead(' '));
+    /// assert_eq!(grid.cells.len(), 9);
+    /// ```
+    pub fn new_filled(width: usize, height: usize, cell: T) -> Self {
+        let (width, height) =
+            Grid::<CellState>::capped_size(width, height, Grid::<CellState>::MAX_CELLS);
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+            alive_glyph: 'X',
+            dead_glyph: ' ',
+            population: 0,
+            rule: Rc::new(ConwayRule),
+            edge_mode: EdgeMode::default(),
+            neighbourhood: Neighbourhood::default(),
+            origin: Point::new(0, 0),
+            previous: Vec::new(),
+            older: Vec::new(),
+            decay: 0,
+            ages: None,
+            heat: None,
+            heat_decay: Grid::<CellState>::DEFAULT_HEAT_DECAY,
+            birth_parity: None,
+            odd_generation: false,
+            history: History::default(),
+        }
+    }
+
+    /// Re-generates the state of the `Grid` `cells` by stepping every `Cell` with its neighbours.
+    ///
+    /// Only the cells change: the `population`, ages and history of a `Grid<CellState>` are
+    /// left alone, as they are only kept by `update_states`.
+    pub fn evolve(&mut self) -> &[T] {
+        let mut next = std::mem::take(&mut self.older);
+        next.clear();
+        let mut neighbours = Vec::with_capacity(MAX_NEIGHBOURS as usize);
+        for (idx, &cell) in self.cells.iter().enumerate() {
+            neighbours.clear();
+            neighbours.extend(self.get_neighbours(self.pos(idx)).map(|p| self[p]));
+            next.push(cell.step(&neighbours));
+        }
+        self.older =
+            std::mem::replace(&mut self.previous, std::mem::replace(&mut self.cells, next));
+        &self.cells
+    }
+}
+
+impl Grid<CellState> {
+    /// Create a new `Grid` of a given `width` and `height`.
+    /// It will default to `X` for alive glyph and ` ` for dead glyph.
+    /// Sizes holding more than `Grid::MAX_CELLS` are scaled down to fit.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::new_empty(3, 3);
+    /// ```
+    pub fn new_empty(width: usize, height: usize) -> Self {
+        let (width, height) = Self::capped_size(width, height, Self::MAX_CELLS);
+        let size = width * height;
+        let cells: Vec<CellState> = (0..size).map(|_| CellState::Dead(' ')).collect();
+        Grid {
+            width,
+            height,
+            cells,
+            ..Default::default()
+        }
+    }
+
+    /// The share of cells alive in a random grid unless another density is given
+    const DEFAULT_DENSITY: f32 = 0.5;
+
+    /// The most cells grids are created with, and resized to, so a huge screen or pattern can't
+    /// allocate without bound. 1,048,576 cells, e.g. 1024 by 1024.
+    /// `GridBuilder::max_cells` lowers it for a single grid.
+    pub const MAX_CELLS: usize = 1 << 20;
+
+    /// Whether a `width` by `height` grid holds at most `Grid::MAX_CELLS`, for rejecting
+    /// patterns read from untrusted input before a grid is allocated for them
+    fn within_cap(width: usize, height: usize) -> bool {
+        width
+            .checked_mul(height)
+            .is_some_and(|cells| cells <= Self::MAX_CELLS)
+    }
+
+    /// Shrinks `width` and `height` in proportion until the grid holds at most `max_cells`,
+    /// keeping them as they are if it already does.
+    /// ```
+    /// use game_of_life::Grid;
+    /// assert_eq!(Grid::capped_size(40, 10, 100), (20, 5));
+    /// assert_eq!(Grid::capped_size(4, 3, 100), (4, 3));
+    /// ```
+    pub fn capped_size(width: usize, height: usize, max_cells: usize) -> (usize, usize) {
+        if width.saturating_mul(height) <= max_cells {
+            return (width, height);
+        }
+        let scale = (max_cells as f64 / width as f64 / height as f64).sqrt();
+        let (mut width, mut height) = (
+            ((width as f64 * scale) as usize).max(1),
+            ((height as f64 * scale) as usize).max(1),
+        );
+        // Rounding can leave a few cells too many, or a side kept at one cell all of them
+        if width.saturating_mul(height) > max_cells {
+            if width >= height {
+                width = max_cells / height;
+            } else {
+                height = max_cells / width;
+            }
+        }
+        (width, height)
+    }
+
+    fn generate_random_cells(
+        rng: &mut fastrand::Rng,
+        size: usize,
+        density: f32,
+        alive_glyph: char,
+        dead_glyph: char,
+    ) -> Vec<CellState> {
+        let density = density.clamp(0.0, 1.0);
+        (0..size)
+            .map(|_| {
+                if rng.f32() < density {
+                    CellState::Alive(alive_glyph)
+                } else {
+                    CellState::Dead(dead_glyph)
+                }
+            })
+            .collect()
+    }
+    /// Generate a new `Grid` of a given `width` and `height` where each cell is alive with a
+    /// probability of `density`, mirrored according to `symmetry`.
+    /// Symmetric soups often settle into pleasing, long-lived patterns.
+    /// ```
+    /// use game_of_life::{Grid, Point, Symmetry};
+    /// let grid = Grid::new_random_symmetric(6, 4, Symmetry::Horizontal, 0.5);
+    /// assert_eq!(grid[Point::new(1, 2)], grid[Point::new(4, 2)]);
+    /// ```
+    pub fn new_random_symmetric(
+        width: usize,
+        height: usize,
+        symmetry: Symmetry,
+        density: f32,
+    ) -> Self {
+        let mut grid = Self::new_random_density(width, height, density);
+        // Every cell copies the first of its images, so only the first half or quarter of the
+        // grid is random and the rest reflects it
+        for idx in 0..grid.cells.len() {
+            let point = grid.pos(idx);
+            // The grid may have been scaled down from the requested size to fit `MAX_CELLS`
+            let source = symmetry
+                .images(point, grid.width as i32, grid.height as i32)
+                .into_iter()
+                .map(|image| grid.idx(&image))
+                .min()
+                .unwrap_or(idx);
+            grid.cells[idx] = grid.cells[source];
+        }
+        grid.population = grid.calculate_population();
+        grid
+    }
+
+    /// Generate a new `Grid` of a given `width` and `height`
+    /// It will be populated with a random distribution of Alive/Dead cells
+    /// The default glyphs of `X` for alive and ` ` for dead.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::new_random(3, 3);
+    /// ```
+    pub fn new_random(width: usize, height: usize) -> Self {
+        Self::new_random_seeded(width, height, fastrand::u64(..))
+    }
+
+    /// Generate a new `Grid` of a given `width` and `height` where each cell is alive with a
+    /// probability of `density`, clamped to `0.0..=1.0`.
+    /// Sparse grids evolve slowly, dense grids churn quickly before dying back.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::new_random_density(3, 3, 1.0);
+    /// assert_eq!(grid.population(), 9);
+    /// ```
+    pub fn new_random_density(width: usize, height: usize, density: f32) -> Self {
+        Self::new_random_with_rng(width, height, density, &mut fastrand::Rng::new())
+    }
+
+    /// Generate a new `Grid` of a given `width` and `height`
+    /// It will be populated with a random distribution of Alive/Dead cells determined by `seed`,
+    /// so the same seed always generates the same grid.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::new_random_seeded(3, 3, 12345);
+    /// assert_eq!(grid.cells, Grid::new_random_seeded(3, 3, 12345).cells);
+    /// ```
+    pub fn new_random_seeded(width: usize, height: usize, seed: u64) -> Self {
+        Self::new_random_with_rng(
+            width,
+            height,
+            Self::DEFAULT_DENSITY,
+            &mut fastrand::Rng::with_seed(seed),
+        )
+    }
+
+    /// Generate a new `Grid` for two color "Immigration" Life, each alive cell of the random
+    /// distribution being `Alive` or `AliveB` with equal chance. Newborn cells take the color of
+    /// most of their parents, so the colors spread and mix as the grid evolves.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::new_random_two_color(3, 3);
+    /// ```
+    pub fn new_random_two_color(width: usize, height: usize) -> Self {
+        let mut rng = fastrand::Rng::new();
+        let mut grid = Self::new_random_with_rng(width, height, Self::DEFAULT_DENSITY, &mut rng);
+        for cell in &mut grid.cells {
+            if cell.is_alive() && rng.bool() {
+                *cell = CellState::AliveB(grid.alive_glyph);
+            }
+        }
+        grid
+    }
+
+    /// Generate a new `Grid` of a given `width` and `height` where each cell is alive with a
+    /// probability of `density`, drawing from `rng`. Passing a seeded `rng` makes the grid
+    /// reproducible, which keeps tests of random grids deterministic.
+    ///
+    /// Like every random grid, it is scaled down to hold at most `Grid::MAX_CELLS`.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut rng = fastrand::Rng::with_seed(7);
+    /// let grid = Grid::new_random_with_rng(3, 3, 0.5, &mut rng);
+    /// ```
+    pub fn new_random_with_rng(
+        width: usize,
+        height: usize,
+        density: f32,
+        rng: &mut fastrand::Rng,
+    ) -> Self {
+        let (width, height) = Self::capped_size(width, height, Self::MAX_CELLS);
+        let default = Self::default();
+        let cells: Vec<CellState> = Self::generate_random_cells(
+            rng,
+            width * height,
+            density,
+            default.alive_glyph,
+            default.dead_glyph,
+        );
+        let mut grid = Grid {
+            width,
+            height,
+            cells,
+            ..default
+        };
+        grid.population = grid.calculate_population();
+        grid
+    }
+
+    /// Generate a new `Grid` of a given `width` and `height`
+    /// It will be populated with a random distribution of Alive/Dead cells
+    /// The glyphs can be overriddne with `alive_glyph` and `dead_glyph`
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::new_random_custom_glyphs(3, 3, '1', '0');
+    /// ```
+    pub fn new_random_custom_glyphs(
+        width: usize,
+        height: usize,
+        alive_glyph: char,
+        dead_glyph: char,
+    ) -> Self {
+        let (width, height) = Self::capped_size(width, height, Self::MAX_CELLS);
+        let cells = Self::generate_random_cells(
+            &mut fastrand::Rng::new(),
+            width * height,
+            Self::DEFAULT_DENSITY,
+            alive_glyph,
+            dead_glyph,
+        );
+        let population = cells.iter().filter(|c| c.is_alive()).count();
+        Grid {
+            width,
+            height,
+            cells,
+            alive_glyph,
+            dead_glyph,
+            population,
+            rule: Rc::new(ConwayRule),
+            edge_mode: EdgeMode::default(),
+            neighbourhood: Neighbourhood::default(),
+            origin: Point::new(0, 0),
+            previous: Vec::new(),
+            older: Vec::new(),
+            decay: 0,
+            ages: None,
+            heat: None,
+            heat_decay: Grid::<CellState>::DEFAULT_HEAT_DECAY,
+            birth_parity: None,
+            odd_generation: false,
+            history: History::default(),
+        }
+    }
+
+    /// Returns the glyphs the cells are displayed with
+    pub fn glyphs(&self) -> CellGlyphs {
+        CellGlyphs {
+            alive: self.alive_glyph,
+            dead: self.dead_glyph,
+        }
+    }
+
+    /// Displays the cells with `glyphs` from now on, updating the cells already on the grid.
+    /// ```
+    /// use game_of_life::{CellGlyphs, Grid};
+    /// let mut grid = Grid::from_plaintext("O.").unwrap();
+    /// grid.set_glyphs(CellGlyphs { alive: '█', dead: '·' });
+    /// assert_eq!(grid.to_string(), "█·\n");
+    /// ```
+    pub fn set_glyphs(&mut self, glyphs: CellGlyphs) {
+        self.alive_glyph = glyphs.alive;
+        self.dead_glyph = glyphs.dead;
+        for cell in &mut self.cells {
+            *cell = match cell {
+                CellState::Alive(_) => CellState::Alive(glyphs.alive),
+                CellState::AliveB(_) => CellState::AliveB(glyphs.alive),
+                CellState::Dead(_) => CellState::Dead(glyphs.dead),
+                CellState::Dying(level) => CellState::Dying(*level),
+            };
+        }
+    }
+
+    /// Like `Display`, but with `alive` and `dead` glyphs chosen for this rendering only.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::from_plaintext(".O\nO.").unwrap();
+    /// assert_eq!(grid.render_to_string('█', '·'), "·█\n█·\n");
+    /// ```
+    pub fn render_to_string(&self, alive: char, dead: char) -> String {
+        let glyphs = CellGlyphs { alive, dead };
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in 0..self.height {
+            let cells = &self.cells[row * self.width..(row + 1) * self.width];
+            out.extend(cells.iter().map(|cell| cell.glyph(glyphs)));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Returns the custom `Ruleset` used to evolve the grid, if any
+    pub fn ruleset(&self) -> Option<Ruleset> {
+        self.rule.ruleset()
+    }
+
+    /// Evolve the grid with the given `Ruleset` instead of the standard Conway rules
+    /// ```
+    /// use game_of_life::{Grid, Ruleset};
+    /// let mut grid = Grid::new_random(3, 3);
+    /// grid.set_ruleset(Ruleset::parse("B36/S23").unwrap());
+    /// ```
+    pub fn set_ruleset(&mut self, ruleset: Ruleset) {
+        self.set_rule(ruleset);
+    }
+
+    /// Returns the rule used to evolve the grid
+    pub fn rule(&self) -> &dyn Rule {
+        self.rule.as_ref()
+    }
+
+    /// Evolve the grid with the given `Rule`, `ConwayRule` by default
+    pub fn set_rule(&mut self, rule: impl Rule + 'static) {
+        self.rule = Rc::new(rule);
+    }
+    /// Returns the number of generations a cell spends `Dying` before it is dead
+    pub fn decay(&self) -> u8 {
+        self.decay
+    }
+
+    /// Let dying cells fade through `decay` generations of `CellState::Dying` before they are
+    /// dead, like the "Generations" family of cellular automata. Dying cells do not count as
+    /// alive neighbours and can not be born again until they are dead.
+    /// A `decay` of `0`, the default, keeps the classic two state behaviour.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::new_random(3, 3);
+    /// grid.set_decay(3);
+    /// ```
+    pub fn set_decay(&mut self, decay: u8) {
+        self.decay = decay;
+    }
+
+    /// Returns how many generations each cell has been continuously alive, in the same order
+    /// as `cells`, or `None` if age tracking is off
+    pub fn ages(&self) -> Option<&[u16]> {
+        self.ages.as_deref()
+    }
+
+    /// Turns tracking how many generations each cell has been continuously alive on or off.
+    /// Newborn cells have an age of `0`, which is reset when they die.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::new_random(3, 3);
+    /// grid.set_age_tracking(true);
+    /// assert!(grid.ages().is_some());
+    /// ```
+    pub fn set_age_tracking(&mut self, enabled: bool) {
+        self.ages = enabled.then(|| vec![0; self.cells.len()]);
+    }
+
+    /// The share of its heat a cell keeps every generation unless set otherwise
+    pub const DEFAULT_HEAT_DECAY: f32 = 0.9;
+
+    /// Returns how much each cell has been alive lately, in the same order as `cells`, or
+    /// `None` if heat tracking is off.
+    ///
+    /// Every generation the heat of each cell is multiplied by the `heat_decay`, then
+    /// increased by `1.0` if the cell is alive. A cell that stays alive approaches a heat of
+    /// `1.0 / (1.0 - heat_decay)`, and moving patterns leave a fading trail behind them.
+    pub fn heat(&self) -> Option<&[f32]> {
+        self.heat.as_deref()
+    }
+
+    /// Turns tracking the heat of each cell on or off, starting every cell cold
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext("OO\nOO").unwrap();
+    /// grid.set_heat_tracking(true);
+    /// grid.update_states();
+    /// assert_eq!(grid.heat(), Some(&[1.0; 4][..]));
+    /// ```
+    pub fn set_heat_tracking(&mut self, enabled: bool) {
+        self.heat = enabled.then(|| vec![0.0; self.cells.len()]);
+    }
+
+    /// Returns the share of its heat a cell keeps every generation
+    pub fn heat_decay(&self) -> f32 {
+        self.heat_decay
+    }
+
+    /// Sets the share of its heat a cell keeps every generation, clamped to `0.0..=0.99` so
+    /// the heat of a cell stays bounded. Lower values fade trails faster.
+    pub fn set_heat_decay(&mut self, decay: f32) {
+        self.heat_decay = decay.clamp(0.0, 0.99);
+    }
+
+    /// Whether each alive cell was born in an odd generation, in the same order as `cells`.
+    /// `false` for dead cells and cells alive since tracking started, and `None` if birth
+    /// parity tracking is off. A single bit a cell, much cheaper than tracking ages.
+    pub fn birth_parity(&self) -> Option<&[bool]> {
+        self.birth_parity.as_deref()
+    }
+
+    /// Turns tracking the parity of the generation each cell was born in on or off
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext("OO\nO.").unwrap();
+    /// grid.set_birth_parity_tracking(true);
+    /// grid.update_states();
+    /// assert_eq!(grid.birth_parity(), Some(&[false, false, false, true][..]));
+    /// ```
+    pub fn set_birth_parity_tracking(&mut self, enabled: bool) {
+        self.birth_parity = enabled.then(|| vec![false; self.cells.len()]);
+    }
+
+    /// Re-generates the state of the `Grid` `cells` based on the rules of Conways game of life
+    ///
+    /// The next generation is computed into the buffer of the oldest generation kept, and the
+    /// buffers are then rotated, so no allocation happens once they have grown to the size of
+    /// the grid.
+    pub fn update_states(&mut self) -> &[CellState] {
+        if self.edge_mode == EdgeMode::Expanding {
+            self.expand_towards_activity();
+        }
+        let mut next = std::mem::take(&mut self.older);
+        next.clear();
+        next.extend(self.cells.iter().enumerate().map(|(idx, cell)| {
+            let state = self.count_neighbours(idx);
+            self.get_cell_state(cell, state)
+        }));
+        self.advance_to(next)
+    }
+
+    /// Like `update_states`, but only evaluates the cells which can change: alive and dying
+    /// cells and the neighbours of alive cells. Much faster on sparse boards.
+    ///
+    /// Rules where dead cells without alive neighbours are born, such as `B0` rulesets, change
+    /// every cell, so the whole grid is evaluated for them. That includes births which depend on
+    /// the number of dead neighbours, which is lower at the edges of bounded grids.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext(".O.\n.O.\n.O.").unwrap();
+    /// grid.update_states_sparse();
+    /// assert_eq!(grid.to_string(), "   \nXXX\n   \n");
+    /// ```
+    pub fn update_states_sparse(&mut self) -> &[CellState] {
+        let dead = CellState::Dead(self.dead_glyph);
+        let births_when_lonely =
+            (0..=self.neighbourhood.directions().len() as u32).any(|dead_count| {
+                let lonely = NeighbourCounts {
+                    alive: 0,
+                    dead: dead_count,
+                };
+                self.rule.next_with_counts(dead, lonely).is_alive()
+            });
+        if births_when_lonely {
+            return self.update_states();
+        }
+        if self.edge_mode == EdgeMode::Expanding {
+            self.expand_towards_activity();
+        }
+        let mut next = std::mem::take(&mut self.older);
+        next.clear();
+        next.extend(self.cells.iter().map(|cell| match cell {
+            CellState::Alive(_) | CellState::AliveB(_) | CellState::Dying(_) => *cell,
+            CellState::Dead(_) => CellState::Dead(self.dead_glyph),
+        }));
+        for (idx, cell) in self.cells.iter().enumerate() {
+            match cell {
+                CellState::Alive(_) | CellState::AliveB(_) => {
+                    let point = self.pos(idx);
+                    for neighbour in self.get_neighbours(point).map(|p| self.idx(&p)) {
+                        let state = self.count_neighbours(neighbour);
+                        next[neighbour] = self.get_cell_state(&self.cells[neighbour], state);
+                    }
+                }
+                CellState::Dying(_) => {}
+                CellState::Dead(_) => continue,
+            }
+            next[idx] = self.get_cell_state(cell, self.count_neighbours(idx));
+        }
+        self.advance_to(next)
+    }
+
+    /// Evolves the grid `generations` times without rendering anything, returning the final
+    /// population. Handy for benchmarks and for checking where a pattern ends up.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext(".O.\n.O.\n.O.").unwrap();
+    /// assert_eq!(grid.run(101), 3);
+    /// ```
+    pub fn run(&mut self, generations: usize) -> usize {
+        for _ in 0..generations {
+            self.update_states_sparse();
+        }
+        self.population
+    }
+
+    /// Makes `next` the current generation, keeping the previous ones for `status`
+    fn advance_to(&mut self, next: Vec<CellState>) -> &[CellState] {
+        self.history.push(&self.cells, self.ages.as_deref());
+        self.older =
+            std::mem::replace(&mut self.previous, std::mem::replace(&mut self.cells, next));
+        if let Some(ages) = &mut self.ages {
+            for ((age, cell), previous) in ages.iter_mut().zip(&self.cells).zip(&self.previous) {
+                *age = match (cell.is_alive(), previous.is_alive()) {
+                    (true, true) => age.saturating_add(1),
+                    _ => 0,
+                };
+            }
+        }
+        if let Some(heat) = &mut self.heat {
+            for (heat, cell) in heat.iter_mut().zip(&self.cells) {
+                *heat = *heat * self.heat_decay + if cell.is_alive() { 1.0 } else { 0.0 };
+            }
+        }
+        self.odd_generation = !self.odd_generation;
+        if let Some(parity) = &mut self.birth_parity {
+            // `previous` now holds the generation the cells were computed from
+            let cells = self.cells.iter().zip(&self.previous);
+            for (parity, (cell, previous)) in parity.iter_mut().zip(cells) {
+                *parity = match (cell.is_alive(), previous.is_alive()) {
+                    (true, false) => self.odd_generation,
+                    (true, true) => *parity,
+                    (false, _) => false,
+                };
+            }
+        }
+        self.population = self.calculate_population();
+        &self.cells
+    }
+
+    /// Forgets the previous generations, after the grid was changed other than by evolving
+    fn forget_history(&mut self) {
+        self.previous.clear();
+        self.older.clear();
+        self.history.clear();
+    }
+
+    /// Returns how many past generations are kept for `step_back`
+    pub fn history_capacity(&self) -> usize {
+        self.history.capacity
+    }
+
+    /// Keeps up to `capacity` past generations for `step_back`, `0` by default.
+    /// The oldest generations are dropped first once the capacity is reached.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history.capacity = capacity;
+        while self.history.states.len() > capacity {
+            self.history.states.pop_front();
+        }
+    }
+
+    /// Restores the generation before the current one, returning `false` if no earlier
+    /// generation is kept. Resizing, clearing or randomizing the grid forgets all generations.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext(".O.\n.O.\n.O.").unwrap();
+    /// grid.set_history_capacity(8);
+    /// let before = grid.cells.clone();
+    /// grid.update_states();
+    /// assert!(grid.step_back());
+    /// assert_eq!(grid.cells, before);
+    /// assert!(!grid.step_back());
+    /// ```
+    pub fn step_back(&mut self) -> bool {
+        let Some((cells, ages)) = self.history.states.pop_back() else {
+            return false;
+        };
+        self.cells = cells;
+        if let (Some(current), Some(ages)) = (&mut self.ages, ages) {
+            *current = ages;
+        }
+        // Only the parity of cells alive on both sides of the step is known for sure
+        self.odd_generation = !self.odd_generation;
+        if let Some(parity) = &mut self.birth_parity {
+            for (parity, cell) in parity.iter_mut().zip(&self.cells) {
+                *parity &= cell.is_alive();
+            }
+        }
+        self.previous.clear();
+        self.older.clear();
+        self.population = self.calculate_population();
+        true
+    }
+
+    /// Resizes the grid to `width` and `height`, keeping the cells in the region the old and new
+    /// sizes overlap, anchored at the top left. New cells are dead.
+    /// The previous generations are forgotten, so the `status` is `Evolving` until the next update.
+    /// Sizes holding more than `Grid::MAX_CELLS` are scaled down to fit.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::new_random(3, 3);
+    /// grid.resize(5, 2);
+    /// assert_eq!(grid.cells.len(), 10);
+    /// ```
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let (width, height) = Self::capped_size(width, height, Self::MAX_CELLS);
+        if (width, height) == (self.width, self.height) {
+            return;
+        }
+        self.resize_anchored(width, height, 0, 0);
+    }
+
+    /// Resizes the grid to `width` and `height`, moving the old cells `left` columns right and
+    /// `top` rows down
+    fn resize_anchored(&mut self, width: usize, height: usize, left: usize, top: usize) {
+        let (old_width, old_height) = (self.width, self.height);
+        // The old position of the cell at `x, y`, if it was on the grid
+        let old = |x: usize, y: usize| {
+            let (x, y) = (x.checked_sub(left)?, y.checked_sub(top)?);
+            (x < old_width && y < old_height).then_some(y * old_width + x)
+        };
+        let dead = CellState::Dead(self.dead_glyph);
+        // Reuse the buffer of an old generation rather than allocating a new one
+        let mut cells = std::mem::take(&mut self.older);
+        cells.clear();
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(old(x, y).map_or(dead, |idx| self.cells[idx]));
+            }
+        }
+        if let Some(ages) = &mut self.ages {
+            *ages = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| old(x, y).map_or(0, |idx| ages[idx]))
+                .collect();
+        }
+        if let Some(heat) = &mut self.heat {
+            heat.clear();
+            heat.resize(width * height, 0.0);
+        }
+        if let Some(parity) = &mut self.birth_parity {
+            *parity = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| old(x, y).is_some_and(|idx| parity[idx]))
+                .collect();
+        }
+        self.older = std::mem::replace(&mut self.cells, cells);
+        self.forget_history();
+        self.width = width;
+        self.height = height;
+        self.population = self.calculate_population();
+    }
+
+    /// The most cells an `EdgeMode::Expanding` grid grows to, about a thousand by a thousand
+    pub const MAX_EXPANDED_CELLS: usize = 1 << 20;
+
+    /// Grows the grid by `EXPAND_MARGIN` dead cells on every side alive cells are within one
+    /// cell of, unless it would then hold more than `MAX_EXPANDED_CELLS`.
+    /// The previous generations are forgotten when it grows, like `resize`.
+    fn expand_towards_activity(&mut self) {
+        let Some((top_left, bottom_right)) = self.live_bounds() else {
+            return;
+        };
+        let grow = |near: bool| if near { EXPAND_MARGIN } else { 0 };
+        let left = grow(top_left.x <= 1);
+        let top = grow(top_left.y <= 1);
+        let right = grow(bottom_right.x as usize + 2 >= self.width);
+        let bottom = grow(bottom_right.y as usize + 2 >= self.height);
+        if left + top + right + bottom == 0 {
+            return;
+        }
+        let (width, height) = (self.width + left + right, self.height + top + bottom);
+        if width * height > Self::MAX_EXPANDED_CELLS {
+            return;
+        }
+        self.resize_anchored(width, height, left, top);
+        self.origin = Point::new(self.origin.x - left as Coord, self.origin.y - top as Coord);
+    }
+
+    /// Brings each cell to life with a probability of `density`, clamped to `0.0..=1.0`, in
+    /// place, keeping the size and settings of the grid.
+    /// The previous generations are forgotten, so the `status` is `Evolving` until the next update.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// grid.randomize(1.0);
+    /// assert_eq!(grid.population(), 9);
+    /// ```
+    pub fn randomize(&mut self, density: f32) {
+        self.randomize_with_rng(density, &mut fastrand::Rng::new());
+    }
+
+    /// Like `randomize`, drawing from `rng`.
+    pub fn randomize_with_rng(&mut self, density: f32, rng: &mut fastrand::Rng) {
+        let density = density.clamp(0.0, 1.0);
+        for cell in &mut self.cells {
+            *cell = if rng.f32() < density {
+                CellState::Alive(self.alive_glyph)
+            } else {
+                CellState::Dead(self.dead_glyph)
+            };
+        }
+        if let Some(ages) = &mut self.ages {
+            ages.fill(0);
+        }
+        if let Some(heat) = &mut self.heat {
+            heat.fill(0.0);
+        }
+        if let Some(parity) = &mut self.birth_parity {
+            parity.fill(false);
+        }
+        self.forget_history();
+        self.population = self.calculate_population();
+    }
+
+    /// Brings each dead cell to life with a probability of `probability`, clamped to
+    /// `0.0..=1.0`, like rain falling on the grid. Unlike `randomize` the alive cells and the
+    /// previous generations are kept, so a little rain every generation keeps the grid from dying
+    /// out without resetting it.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// grid.sprinkle(1.0);
+    /// assert_eq!(grid.population(), 9);
+    /// ```
+    pub fn sprinkle(&mut self, probability: f32) {
+        self.sprinkle_with_rng(probability, &mut fastrand::Rng::new());
+    }
+
+    /// Like `sprinkle`, drawing from `rng`.
+    pub fn sprinkle_with_rng(&mut self, probability: f32, rng: &mut fastrand::Rng) {
+        let probability = probability.clamp(0.0, 1.0);
+        for cell in &mut self.cells {
+            if matches!(cell, CellState::Dead(_)) && rng.f32() < probability {
+                *cell = CellState::Alive(self.alive_glyph);
+                self.population += 1;
+            }
+        }
+    }
+
+    /// Kills every cell in place, keeping the size and settings of the grid.
+    /// The previous generations are forgotten, so the `status` is `Evolving` until the next update.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::new_random(3, 3);
+    /// grid.clear();
+    /// assert_eq!(grid.population(), 0);
+    /// ```
+    pub fn clear(&mut self) {
+        self.cells.fill(CellState::Dead(self.dead_glyph));
+        if let Some(ages) = &mut self.ages {
+            ages.fill(0);
+        }
+        if let Some(heat) = &mut self.heat {
+            heat.fill(0.0);
+        }
+        if let Some(parity) = &mut self.birth_parity {
+            parity.fill(false);
+        }
+        self.forget_history();
+        self.population = 0;
+    }
+
+    /// Compares the grid with the previous generations to tell whether it is still evolving.
+    /// Only still lifes and period 2 oscillators, such as blinkers, are detected as settled.
+    /// ```
+    /// use game_of_life::{Grid, GridStatus};
+    /// let mut grid = Grid::from_plaintext("OO\nOO").unwrap();
+    /// grid.update_states();
+    /// assert_eq!(grid.status(), GridStatus::Still);
+    /// ```
+    pub fn status(&self) -> GridStatus {
+        if self.cells == self.previous {
+            GridStatus::Still
+        } else if self.cells == self.older {
+            GridStatus::Oscillating
+        } else {
+            GridStatus::Evolving
+        }
+    }
+
+    /// Steps the grid up to `max_period` generations looking for a state it was already in,
+    /// returning the period of the cycle it has settled into, e.g. `1` for still lifes and `2`
+    /// for blinkers. `None` if no state repeated within `max_period` generations.
+    ///
+    /// Note that this advances the grid by the generations it stepped.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext(".O.\n.O.\n.O.").unwrap();
+    /// assert_eq!(grid.detect_period(10), Some(2));
+    /// ```
+    pub fn detect_period(&mut self, max_period: usize) -> Option<usize> {
+        let mut snapshots = vec![self.cells.clone()];
+        for generation in 1..=max_period {
+            self.update_states();
+            if let Some(seen) = snapshots.iter().position(|cells| *cells == self.cells) {
+                return Some(generation - seen);
+            }
+            snapshots.push(self.cells.clone());
+        }
+        None
+    }
+
+    /// Steps the grid until its `status` is still or oscillating, at most `max_gen` generations.
+    ///
+    /// Useful to study random soups: how long they live and what they leave behind.
+    /// ```
+    /// use game_of_life::{Grid, SoupResult};
+    /// let mut grid = Grid::from_plaintext("OO.\nO..").unwrap();
+    /// let result = grid.run_until_stable(100);
+    /// assert_eq!(
+    ///     result,
+    ///     SoupResult { generations: 2, final_population: 4, stabilized: true }
+    /// );
+    /// ```
+    pub fn run_until_stable(&mut self, max_gen: usize) -> SoupResult {
+        for generation in 1..=max_gen {
+            self.update_states();
+            if self.status() != GridStatus::Evolving {
+                return SoupResult {
+                    generations: generation,
+                    final_population: self.population,
+                    stabilized: true,
+                };
+            }
+        }
+        SoupResult {
+            generations: max_gen,
+            final_population: self.population,
+            stabilized: false,
+        }
+    }
+
+    /// Sets every cell of the rectangle between the corners `top_left` and `bottom_right` to
+    /// `state`, keeping the `population` up to date. The corners are included and may be given
+    /// in any order, and the parts of the rectangle outside the grid are clipped.
+    /// ```
+    /// use game_of_life::{CellState, Grid, Point};
+    /// let mut grid = Grid::new_empty(4, 4);
+    /// grid.fill_region(Point::new(2, 2), Point::new(5, 0), CellState::Alive('X'));
+    /// assert_eq!(grid.population(), 6);
+    /// ```
+    pub fn fill_region(&mut self, top_left: Point, bottom_right: Point, state: CellState) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let (max_x, max_y) = (self.width as Coord - 1, self.height as Coord - 1);
+        let (min_x, max_x) = (
+            top_left.x.min(bottom_right.x).max(0),
+            top_left.x.max(bottom_right.x).min(max_x),
+        );
+        let (min_y, max_y) = (
+            top_left.y.min(bottom_right.y).max(0),
+            top_left.y.max(bottom_right.y).min(max_y),
+        );
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                self.set_cell(Point::new(x, y), state);
+            }
+        }
+    }
+
+    /// Sets the cell at `p` to `state`, keeping the `population` up to date.
+    /// Returns `false` without changing anything if `p` is outside the grid.
+    /// ```
+    /// use game_of_life::{CellState, Grid, Point};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// assert!(grid.set_cell(Point::new(1, 1), CellState::Alive('X')));
+    /// assert!(!grid.set_cell(Point::new(3, 3), CellState::Alive('X')));
+    /// ```
+    pub fn set_cell(&mut self, p: Point, state: CellState) -> bool {
+        if !self.contains(&p) {
+            return false;
+        }
+        let idx = self.idx(&p);
+        match (self.cells[idx].is_alive(), state.is_alive()) {
+            (false, true) => self.population += 1,
+            (true, false) => self.population -= 1,
+            _ => {}
+        }
+        self.cells[idx] = state;
+        if let Some(age) = self.ages.as_mut().and_then(|ages| ages.get_mut(idx)) {
+            *age = 0;
+        }
+        true
+    }
+
+    /// Flips the cell at `p` between alive and dead.
+    /// Returns `false` without changing anything if `p` is outside the grid.
+    pub fn toggle_cell(&mut self, p: Point) -> bool {
+        let state = match self.try_get(p) {
+            Some(CellState::Alive(_) | CellState::AliveB(_)) => CellState::Dead(self.dead_glyph),
+            Some(CellState::Dead(_) | CellState::Dying(_)) => CellState::Alive(self.alive_glyph),
+            None => return false,
+        };
+        self.set_cell(p, state)
+    }
+
+    /// Copies the alive cells of `other` onto the grid with its top left corner at `at`.
+    /// Cells which fall outside the grid are clipped, and cells which are already alive stay
+    /// alive where `other` has dead cells. Alive cells keep their color.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let mut grid = Grid::new_empty(10, 10);
+    /// let glider = Grid::from_plaintext(".O\n..O\nOOO").unwrap();
+    /// grid.insert(&glider, Point::new(4, 4));
+    /// assert_eq!(grid.population(), 5);
+    /// ```
+    pub fn insert(&mut self, other: &Grid<CellState>, at: Point) {
+        for p in other.alive_points() {
+            let alive = match other[p] {
+                CellState::AliveB(_) => CellState::AliveB(self.alive_glyph),
+                _ => CellState::Alive(self.alive_glyph),
+            };
+            self.set_cell(at + p, alive);
+        }
+    }
+
+    /// Returns the points of the alive cells, row by row from the top left.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let grid = Grid::from_plaintext(".O\nO.").unwrap();
+    /// let points: Vec<Point> = grid.alive_points().collect();
+    /// assert_eq!(points, [Point::new(1, 0), Point::new(0, 1)]);
+    /// ```
+    pub fn alive_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_alive())
+            .map(|(idx, _)| self.pos(idx))
+    }
+
+    /// Copies the `width` by `height` region with its top left corner at `top_left` into a new
+    /// grid with the same glyphs, treating the parts of the region outside the grid as dead.
+    /// Regions of more than `Grid::MAX_CELLS` cells are shrunk like `Grid::new_empty` grids.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let grid = Grid::from_plaintext("O..\n.OO").unwrap();
+    /// let cropped = grid.crop(Point::new(1, 0), 3, 3);
+    /// assert_eq!(cropped.to_string(), "   \nXX \n   \n");
+    /// ```
+    pub fn crop(&self, top_left: Point, width: usize, height: usize) -> Grid<CellState> {
+        let (width, height) = Self::capped_size(width, height, Self::MAX_CELLS);
+        let mut cropped = Grid {
+            width,
+            height,
+            cells: vec![CellState::Dead(self.dead_glyph); width * height],
+            alive_glyph: self.alive_glyph,
+            dead_glyph: self.dead_glyph,
+            ..Default::default()
+        };
+        for idx in 0..cropped.cells.len() {
+            if let Some(&cell) = self.try_get(top_left + cropped.pos(idx)) {
+                cropped.cells[idx] = cell;
+            }
+        }
+        cropped.population = cropped.calculate_population();
+        cropped
+    }
+
+    /// Mirrors the grid left to right.
+    /// Like all transforms, the previous generations are forgotten.
+    pub fn flip_horizontal(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.remap(width, height, |x, y| y * width + (width - 1 - x));
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.remap(width, height, |x, y| (height - 1 - y) * width + x);
+    }
+
+    /// Rotates the grid a quarter turn clockwise, swapping its width and height.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let mut grid = Grid::from_plaintext("OO.").unwrap();
+    /// grid.rotate_cw();
+    /// assert_eq!(grid.to_string(), "X\nX\n \n");
+    /// ```
+    pub fn rotate_cw(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.remap(height, width, |x, y| (height - 1 - x) * width + y);
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise, swapping its width and height.
+    pub fn rotate_ccw(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.remap(height, width, |x, y| x * width + (width - 1 - y));
+    }
+
+    /// Rebuilds the grid as `width` by `height`, taking each cell and its age from the index
+    /// `source` returns for its new position.
+    fn remap(&mut self, width: usize, height: usize, source: impl Fn(usize, usize) -> usize) {
+        let positions = || (0..height).flat_map(|y| (0..width).map(move |x| (x, y)));
+        self.cells = positions().map(|(x, y)| self.cells[source(x, y)]).collect();
+        if let Some(ages) = &mut self.ages {
+            *ages = positions().map(|(x, y)| ages[source(x, y)]).collect();
+        }
+        if let Some(heat) = &mut self.heat {
+            *heat = positions().map(|(x, y)| heat[source(x, y)]).collect();
+        }
+        if let Some(parity) = &mut self.birth_parity {
+            *parity = positions().map(|(x, y)| parity[source(x, y)]).collect();
+        }
+        self.width = width;
+        self.height = height;
+        self.forget_history();
+    }
+
+    /// Copies the alive cells into a new `width` by `height` grid, centered on their bounding box.
+    /// Patterns larger than the new grid are clipped evenly on both sides, any odd cell of space
+    /// or clipping going to the right and bottom.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let blinker = Grid::from_plaintext("OOO").unwrap();
+    /// assert_eq!(blinker.centered_in(5, 3).to_string(), "     \n XXX \n     \n");
+    /// ```
+    pub fn centered_in(&self, width: usize, height: usize) -> Grid<CellState> {
+        let (width, height) = Self::capped_size(width, height, Self::MAX_CELLS);
+        let Some((min, max)) = self.live_bounds() else {
+            return self.crop(Point::new(0, 0), width, height);
+        };
+        let offset = |target: usize, low: Coord, high: Coord| {
+            let target = Coord::try_from(target).unwrap_or(Coord::MAX);
+            let spare = target.saturating_sub(high - low + 1);
+            low - spare / 2
+        };
+        let top_left = Point::new(offset(width, min.x, max.x), offset(height, min.y, max.y));
+        self.crop(top_left, width, height)
+    }
+
+    /// Returns the top left and bottom right corners of the smallest rectangle holding every
+    /// alive cell, both inclusive, or `None` if no cell is alive.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let grid = Grid::from_plaintext("...\n.O.\n..O").unwrap();
+    /// assert_eq!(grid.live_bounds(), Some((Point::new(1, 1), Point::new(2, 2))));
+    /// ```
+    pub fn live_bounds(&self) -> Option<(Point, Point)> {
+        self.alive_points().fold(None, |bounds, p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )),
+        })
+    }
+
+    /// Groups the alive cells into clusters of cells touching each other, diagonals included,
+    /// wrapping around the edges of toroidal grids. Clusters are ordered by their first cell
+    /// in `cells`, which comes first in its cluster.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let grid = Grid::from_plaintext("O..\n.O.\n...\n..O").unwrap();
+    /// let clusters = grid.connected_components();
+    /// assert_eq!(clusters.len(), 2);
+    /// assert_eq!(clusters[1], [Point::new(2, 3)]);
+    /// ```
+    pub fn connected_components(&self) -> Vec<Vec<Point>> {
+        let mut seen = vec![false; self.cells.len()];
+        let mut components = Vec::new();
+        // Flood filled from a stack rather than recursively, so large clusters can't overflow
+        let mut stack = Vec::new();
+        for start in 0..self.cells.len() {
+            if seen[start] || !self.cells[start].is_alive() {
+                continue;
+            }
+            seen[start] = true;
+            stack.push(start);
+            let mut component = Vec::new();
+            while let Some(idx) = stack.pop() {
+                let point = self.pos(idx);
+                component.push(point);
+                for neighbour in ORTHO_PLUS_DIR.iter().filter_map(|&d| self.wrap(point + d)) {
+                    let neighbour = self.idx(&neighbour);
+                    if !seen[neighbour] && self.cells[neighbour].is_alive() {
+                        seen[neighbour] = true;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// The clusters of `connected_components` which are spaceships: evolved on their own, they
+    /// take the same shape again somewhere else within `max_period` generations. Clusters of
+    /// ships which collided change shape instead, so they are not included.
+    /// ```
+    /// use game_of_life::{patterns, Grid, Point};
+    /// let mut grid = Grid::new_empty(10, 10);
+    /// grid.insert(&patterns::glider(), Point::new(1, 1));
+    /// grid.insert(&patterns::block(), Point::new(6, 6));
+    /// assert_eq!(grid.moving_components(4).len(), 1);
+    /// ```
+    pub fn moving_components(&self, max_period: usize) -> Vec<Vec<Point>> {
+        self.connected_components()
+            .into_iter()
+            .filter(|component| self.is_moving(component, max_period))
+            .collect()
+    }
+
+    /// Whether the cells of `component` travel, evolved with the rules of this grid without
+    /// anything else around them
+    fn is_moving(&self, component: &[Point], max_period: usize) -> bool {
+        let (_, shape) = normalized_shape(component.iter().copied());
+        let (width, height) = shape
+            .iter()
+            .fold((0, 0), |(w, h), p| (w.max(p.x + 1), h.max(p.y + 1)));
+        // Nothing travels faster than one cell a generation, so a ship stays within this margin
+        let margin = max_period as Coord + 1;
+        let mut alone = Grid::new_empty(
+            (width + 2 * margin) as usize,
+            (height + 2 * margin) as usize,
+        );
+        alone.rule = self.rule.clone();
+        alone.neighbourhood = self.neighbourhood;
+        for p in &shape {
+            alone.set_cell(
+                Point::new(p.x + margin, p.y + margin),
+                CellState::Alive(self.alive_glyph),
+            );
+        }
+        let start_in_alone = Point::new(margin, margin);
+        for _ in 0..max_period {
+            alone.update_states_sparse();
+            if alone.population() != shape.len() {
+                continue;
+            }
+            let (at, now) = normalized_shape(alone.alive_points());
+            if now == shape {
+                return at != start_in_alone;
+            }
+        }
+        false
+    }
+
+    /// Population of the grid i.e number of alive cells.
+    ///
+    /// The count is cached rather than rescanned, so it is cheap to call every frame. Cells
+    /// changed through `IndexMut` or `cells` are only counted after the next `update_states`.
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    /// Counts the alive neighbours of the cell at `p`, following the `Neighbourhood` and
+    /// `EdgeMode` of the grid. Points outside the grid only count the neighbours inside it.
+    /// ```
+    /// use game_of_life::{Grid, Point};
+    /// let grid = Grid::from_plaintext("OOO").unwrap();
+    /// assert_eq!(grid.alive_neighbours(Point::new(1, 0)), 2);
+    /// ```
+    pub fn alive_neighbours(&self, p: Point) -> u32 {
+        self.get_neighbours_state(p).alive as u32
+    }
+
+    /// Counts the alive neighbours of every cell at once, in the same order as `cells`,
+    /// following the `Neighbourhood` and `EdgeMode` of the grid
+    /// ```
+    /// use game_of_life::Grid;
+    /// let grid = Grid::from_plaintext("OOO").unwrap();
+    /// assert_eq!(grid.neighbour_counts(), [1, 2, 1]);
+    /// ```
+    pub fn neighbour_counts(&self) -> Vec<u8> {
+        (0..self.cells.len())
+            .map(|idx| self.count_neighbours(idx).alive as u8)
+            .collect()
+    }
+
+    fn calculate_population(&self) -> usize {
+        self.cells.iter().filter(|c| c.is_alive()).count()
+    }
+    /// Gets the new state of the current cell from the `Rule` of the grid.
+    ///
+    /// With a `decay` cells which die are `Dying` for that many generations first.
+    /// Surviving cells keep their color while newborn cells take the color of most of their
+    /// parents, `Alive` on a tie.
+    fn get_cell_state(&self, cell: &CellState, state: NeighbourState) -> CellState {
+        if let CellState::Dying(level) = *cell {
+            return if level > 1 {
+                CellState::Dying(level - 1)
+            } else {
+                CellState::Dead(self.dead_glyph)
+            };
+        }
+        match self.rule.next_with_counts(*cell, state.counts()) {
+            CellState::Alive(_) | CellState::AliveB(_) => {
+                let color_b = match cell {
+                    CellState::Alive(_) => false,
+                    CellState::AliveB(_) => true,
+                    _ => state.alive_b * 2 > state.alive,
+                };
+                if color_b {
+                    CellState::AliveB(self.alive_glyph)
+                } else {
+                    CellState::Alive(self.alive_glyph)
+                }
+            }
+            CellState::Dead(_) if cell.is_alive() => self.dying(),
+            CellState::Dead(_) => CellState::Dead(self.dead_glyph),
+            dying @ CellState::Dying(_) => dying,
+        }
+    }
+
+    /// The state of a cell which was alive and just died
+    fn dying(&self) -> CellState {
+        if self.decay > 0 {
+            CellState::Dying(self.decay)
+        } else {
+            CellState::Dead(self.dead_glyph)
+        }
+    }
+    /// Fast path of `get_neighbours_state` for the cell at `idx` used by `update_states`.
+    /// Cells away from the edges index their neighbours directly, skipping the bounds checks.
+    fn count_neighbours(&self, idx: usize) -> NeighbourState {
+        let width = self.width;
+        let (x, y) = (idx % width, idx / width);
+        if x == 0 || y == 0 || x + 1 == width || y + 1 == self.height {
+            return self.get_neighbours_state(self.pos(idx));
+        }
+        let moore = [
+            idx - width - 1,
+            idx - width,
+            idx - width + 1,
+            idx - 1,
+            idx + 1,
+            idx + width - 1,
+            idx + width,
+            idx + width + 1,
+        ];
+        let von_neumann = [idx - width, idx - 1, idx + 1, idx + width];
+        let neighbours: &[usize] = match self.neighbourhood {
+            Neighbourhood::Moore => &moore,
+            Neighbourhood::VonNeumann => &von_neumann,
+        };
+        let mut alive = 0;
+        let mut alive_b = 0;
+        for &neighbour in neighbours {
+            match self.cells[neighbour] {
+                CellState::Alive(_) => alive += 1,
+                CellState::AliveB(_) => {
+                    alive += 1;
+                    alive_b += 1;
+                }
+                CellState::Dead(_) | CellState::Dying(_) => {}
+            }
+        }
+        NeighbourState {
+            alive,
+            dead: neighbours.len() as i32 - alive,
+            alive_b,
+        }
+    }
+    fn get_neighbours_state(&self, point: Point) -> NeighbourState {
+        let mut alive = 0;
+        let mut dead = 0;
+        let mut alive_b = 0;
+        // Neighbours outside the bounds of the grid are skipped by `get_neighbours`
+        for neighbour in self.get_neighbours(point) {
+            match self[neighbour] {
+                CellState::Alive(_) => alive += 1,
+                CellState::AliveB(_) => {
+                    alive += 1;
+                    alive_b += 1;
+                }
+                CellState::Dead(_) | CellState::Dying(_) => dead += 1,
+            }
+        }
+        NeighbourState {
+            alive,
+            dead,
+            alive_b,
+        }
+    }
+}
+
+impl Default for Grid<CellState> {
+    fn default() -> Self {
+        Grid::new_filled(10, 10, CellState::Dead(' '))
+    }
+}
+
+impl Display for Grid<CellState> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height {
+            for w in row * self.width..(row + 1) * self.width {
+                write!(f, "{}", self.cells[w])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `ParseGridError` describes why a grid could not be parsed from its `Display` format
+pub enum ParseGridError {
+    /// A row is not as wide as the first row
+    RaggedRow {
+        /// The row of the grid, starting from `0`
+        row: usize,
+        /// The width of the first row
+        expected: usize,
+        /// The width of this row
+        found: usize,
+    },
+    /// A cell is not one of the recognised glyphs
+    InvalidCharacter {
+        /// The row of the grid the character is on, starting from `0`
+        row: usize,
+        /// The unexpected character
+        character: char,
+    },
+    /// The grid holds more than `Grid::MAX_CELLS` cells
+    TooLarge {
+        /// The width of the rows
+        width: usize,
+        /// The number of rows
+        height: usize,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {row} is {found} cells wide, expected {expected}"),
+            ParseGridError::InvalidCharacter { row, character } => {
+                write!(f, "invalid character '{character}' in row {row}")
+            }
+            ParseGridError::TooLarge { width, height } => write!(
+                f,
+                "grid of {width} by {height} cells is larger than the {} cells a grid holds",
+                Grid::MAX_CELLS
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+/// Parses the `Display` format of a grid back into a grid with the default glyphs.
+/// `X`, `0`, `O` and `*` are alive cells, spaces and `.` are dead cells and the shades
+/// `░▒▓` are dying cells. The width is that of the first row, which every row must match.
+/// ```
+/// use game_of_life::Grid;
+/// let grid: Grid<_> = " X \n X \n X \n".parse().unwrap();
+/// assert_eq!(grid.population(), 3);
+/// ```
+impl FromStr for Grid<CellState> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s.lines().collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if !Grid::within_cap(width, rows.len()) {
+            return Err(ParseGridError::TooLarge {
+                width,
+                height: rows.len(),
+            });
+        }
+        let mut grid = Grid::new_empty(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            let found = row.chars().count();
+            if found != width {
+                return Err(ParseGridError::RaggedRow {
+                    row: y,
+                    expected: width,
+                    found,
+                });
+            }
+            for (x, character) in row.chars().enumerate() {
+                grid.cells[y * width + x] = match character {
+                    'X' | '0' | 'O' | '*' => CellState::Alive(grid.alive_glyph),
+                    ' ' | '.' => CellState::Dead(grid.dead_glyph),
+                    _ => match CellState::DYING_GLYPHS.iter().position(|&c| c == character) {
+                        Some(shade) => CellState::Dying(shade as u8 + 1),
+                        None => return Err(ParseGridError::InvalidCharacter { row: y, character }),
+                    },
+                };
+            }
+        }
+        grid.population = grid.calculate_population();
+        Ok(grid)
+    }
+}
+
+/// The top left corner of the bounding box of `points`, and the points relative to it sorted
+/// row by row, so equal shapes compare equal wherever they are
+fn normalized_shape(points: impl Iterator<Item = Point>) -> (Point, Vec<Point>) {
+    let points: Vec<Point> = points.collect();
+    let corner = Point::new(
+        points.iter().map(|p| p.x).min().unwrap_or(0),
+        points.iter().map(|p| p.y).min().unwrap_or(0),
+    );
+    let mut shape: Vec<Point> = points
+        .iter()
+        .map(|p| Point::new(p.x - corner.x, p.y - corner.y))
+        .collect();
+    shape.sort_by_key(|p| (p.y, p.x));
+    (corner, shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_try_get() {
+        let g = Grid::new_empty(0, 0);
+        assert!(g.try_get(Point { x: 10, y: 10 }).is_none());
+    }
+
+    #[test]
+    fn test_degenerate_sizes() {
+        for (width, height) in [(0, 0), (0, 4), (4, 0)] {
+            let mut g = Grid::new_random(width, height);
+            assert!(g.cells.is_empty());
+            assert_eq!(g.population(), 0);
+            assert_eq!(g.pos(0), Point::new(0, 0));
+            assert!(!g.contains(&Point::new(0, 0)));
+            assert_eq!(g.try_get(Point::new(0, 0)), None);
+            assert_eq!(g.get_neighbours(Point::new(0, 0)).count(), 0);
+            assert_eq!(g.alive_neighbours(Point::new(0, 0)), 0);
+            assert!(g.neighbour_counts().is_empty());
+            assert_eq!(g.live_bounds(), None);
+            assert!(g.alive_points().next().is_none());
+            g.set_cell(Point::new(0, 0), CellState::Alive('X'));
+            g.toggle_cell(Point::new(1, 1));
+            g.fill_region(Point::new(0, 0), Point::new(2, 2), CellState::Alive('X'));
+            g.insert(&Grid::from_plaintext("OO").unwrap(), Point::new(0, 0));
+            g.sprinkle(1.0);
+            g.randomize(1.0);
+            assert!(g.update_states().is_empty());
+            assert!(g.update_states_sparse().is_empty());
+            assert_eq!(g.run(3), 0);
+            assert_eq!(g.status(), GridStatus::Still);
+            g.set_edge_mode(EdgeMode::Toroidal);
+            g.update_states();
+            g.set_edge_mode(EdgeMode::Expanding);
+            g.update_states();
+            g.set_neighbourhood(Neighbourhood::VonNeumann);
+            g.update_states();
+            g.set_age_tracking(true);
+            g.set_heat_tracking(true);
+            g.set_history_capacity(2);
+            g.update_states();
+            g.step_back();
+            g.flip_horizontal();
+            g.flip_vertical();
+            g.rotate_cw();
+            g.rotate_ccw();
+            assert_eq!((g.width * g.height, g.cells.len()), (0, 0));
+            g.evolve();
+            g.detect_period(4);
+            g.clear();
+            assert_eq!(g.to_string(), "\n".repeat(height));
+            assert_eq!(g.render_to_string('X', ' '), "\n".repeat(height));
+            assert_eq!(g.crop(Point::new(0, 0), 2, 2).population(), 0);
+            assert_eq!(g.centered_in(3, 3).population(), 0);
+        }
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let mut g = Grid::from_plaintext("OO....\nOO....\n......\n...OO.\n...OO.").unwrap();
+        let components = g.connected_components();
+        assert_eq!(components.len(), 2);
+        assert!(components.iter().all(|component| component.len() == 4));
+        assert!(components[0].contains(&Point::new(1, 1)));
+        assert!(components[1].contains(&Point::new(4, 4)));
+
+        // A diagonal touch joins the blocks
+        g.set_cell(Point::new(2, 2), CellState::Alive('X'));
+        assert_eq!(g.connected_components().len(), 1);
+        assert_eq!(g.connected_components()[0].len(), 9);
+
+        // So does the edge of a toroidal grid
+        let mut g = Grid::from_plaintext("O...O\n.....\n..O..").unwrap();
+        assert_eq!(g.connected_components().len(), 3);
+        g.set_edge_mode(EdgeMode::Toroidal);
+        assert_eq!(g.connected_components().len(), 2);
+        assert!(Grid::new_empty(3, 3).connected_components().is_empty());
+
+        // Large clusters don't overflow the stack
+        let full = Grid::new_random_density(1000, 1000, 1.0);
+        assert_eq!(full.connected_components()[0].len(), 1_000_000);
+    }
+
+    #[test]
+    fn test_moving_components() {
+        let mut g = Grid::new_empty(20, 20);
+        g.insert(&patterns::glider(), Point::new(2, 2));
+        g.insert(&patterns::blinker(), Point::new(14, 2));
+        g.insert(&patterns::block(), Point::new(2, 14));
+        for _ in 0..4 {
+            g.update_states();
+        }
+        let moving = g.moving_components(4);
+        assert_eq!(moving.len(), 1);
+        assert_eq!(moving[0].len(), 5);
+        assert!(g.moving_components(1).is_empty());
+
+        // Two gliders headed for each other stop being ships once they collide
+        let mut g = Grid::from_plaintext(
+            ".O.......\n..O......\nOOO......\n.........\n.........\n......OOO\n......O..\n.......O.",
+        )
+        .unwrap();
+        assert_eq!(g.moving_components(4).len(), 2);
+        for _ in 0..5 {
+            g.update_states();
+        }
+        assert_eq!(g.connected_components().len(), 1);
+        assert!(g.moving_components(4).is_empty());
+    }
+
+    #[test]
+    fn test_grid_new_random() {
+        let rand_g = Grid::new_random(10, 10);
+        assert_eq!(rand_g.cells.len(), 100);
+    }
+
+    #[test]
+    fn test_get_neighbours_state() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[1] = CellState::Alive(g.alive_glyph);
+        // x 0 x
+        // x x x
+        // x x x
+        let state = g.get_neighbours_state(Point { x: 0, y: 0 });
+        assert_eq!(state.dead, 2);
+        assert_eq!(state.alive, 1);
+    }
+
+    #[test]
+    fn test_get_neighbours_state_unknown_point() {
+        let g = Grid::new_empty(3, 3);
+        let state = g.get_neighbours_state(Point { x: 5, y: 5 });
+        assert_eq!(state.dead, 0);
+        assert_eq!(state.alive, 0);
+    }
+
+    #[test]
+    fn test_alive_neighbours() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[1] = CellState::Alive(g.alive_glyph);
+        assert_eq!(g.alive_neighbours(Point::new(0, 0)), 1);
+        assert_eq!(g.alive_neighbours(Point::new(1, 0)), 0);
+        assert_eq!(g.alive_neighbours(Point::new(5, 5)), 0);
+
+        let mut g = Grid::new_empty(3, 3);
+        g.set_edge_mode(EdgeMode::Toroidal);
+        g.cells[8] = CellState::Alive(g.alive_glyph);
+        assert_eq!(g.alive_neighbours(Point::new(0, 0)), 1);
+    }
+
+    #[test]
+    fn test_neighbour_counts() {
+        let g = Grid::from_plaintext(".O..\n..O.\nOOO.").unwrap();
+        let counts = g.neighbour_counts();
+        assert_eq!(counts.len(), 12);
+        assert_eq!(counts[0], 1);
+        assert_eq!(counts[g.idx(&Point::new(1, 1))], 5);
+        assert_eq!(counts[g.idx(&Point::new(3, 1))], 2);
+        assert_eq!(counts[g.idx(&Point::new(2, 2))], 2);
+        for (idx, &count) in counts.iter().enumerate() {
+            assert_eq!(u32::from(count), g.alive_neighbours(g.pos(idx)));
+        }
+
+        let mut g = Grid::from_plaintext("O..\n...\n..O").unwrap();
+        g.set_edge_mode(EdgeMode::Toroidal);
+        assert_eq!(g.neighbour_counts()[0], 1);
+        g.set_neighbourhood(Neighbourhood::VonNeumann);
+        assert_eq!(g.neighbour_counts()[0], 0);
+        assert_eq!(g.neighbour_counts()[2], 2);
+    }
+
+    #[test]
+    fn test_grid_display() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[4] = CellState::Alive('X');
+        let s = format!("{g}");
+        assert_eq!(s, "   \n X \n   \n".to_string());
+    }
+
+    #[test]
+    fn test_grid_from_str_round_trip() {
+        let mut g = Grid::new_random(7, 4);
+        g.set_decay(3);
+        g.update_states();
+        let parsed: Grid<CellState> = g.to_string().parse().unwrap();
+        assert_eq!((parsed.width, parsed.height), (7, 4));
+        assert_eq!(parsed.cells, g.cells);
+        assert_eq!(parsed.population(), g.population());
+
+        let alternative: Grid<CellState> = ".0.\nO*.".parse().unwrap();
+        assert_eq!(alternative.population(), 3);
+    }
+
+    #[test]
+    fn test_grid_from_str_errors() {
+        assert_eq!(
+            "XX\nX".parse::<Grid<CellState>>().unwrap_err(),
+            ParseGridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            "X?".parse::<Grid<CellState>>().unwrap_err(),
+            ParseGridError::InvalidCharacter {
+                row: 0,
+                character: '?'
+            }
+        );
+        let huge = format!("{}\n{}", " ".repeat(1100), "\n".repeat(1000));
+        assert_eq!(
+            huge.parse::<Grid<CellState>>().unwrap_err(),
+            ParseGridError::TooLarge {
+                width: 1100,
+                height: 1001
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_glyphs() {
+        let mut g = Grid::new_empty(3, 1);
+        g.set_cell(Point::new(1, 0), CellState::Alive(g.alive_glyph));
+        assert_eq!(g.to_string(), " X \n");
+        g.set_glyphs(CellGlyphs {
+            alive: '0',
+            dead: '.',
+        });
+        assert_eq!(g.glyphs().alive, '0');
+        assert_eq!(g.to_string(), ".0.\n");
+        assert_eq!(g.calculate_population(), 1);
+        g.update_states();
+        assert_eq!(g.to_string(), "...\n");
+    }
+
+    #[test]
+    fn test_render_to_string() {
+        let mut g = Grid::new_empty(3, 2);
+        g.set_decay(2);
+        g.set_cell(Point::new(0, 0), CellState::Alive(g.alive_glyph));
+        g.set_cell(Point::new(2, 1), CellState::Dying(1));
+        assert_eq!(g.render_to_string('0', '.'), "0..\n..░\n");
+        assert_eq!(g.to_string(), "X  \n  ░\n");
+    }
+
+    #[test]
+    fn test_grid_debug() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[4] = CellState::Alive('X');
+        let s = format!("{g:?}");
+        assert_eq!(s, "Grid { width: 3, height: 3, cells: [Dead(' '), Dead(' '), Dead(' '), Dead(' '), Alive('X'), Dead(' '), Dead(' '), Dead(' '), Dead(' ')], dead_glyph: ' ', alive_glyph: 'X', population: 0, rule: ConwayRule, edge_mode: Bounded, neighbourhood: Moore, origin: Point { x: 0, y: 0 }, previous: [], older: [], decay: 0, ages: None, heat: None, heat_decay: 0.9, birth_parity: None, odd_generation: false, history: History { capacity: 0, states: [] } }".to_string());
+    }
+
+    #[test]
+    fn test_update_state() {
+        let mut g = Grid::new_random(10, 10);
+        g.update_states();
+    }
+
+    #[test]
+    fn test_get_cell_state() {
+        let g = Grid::new_empty(3, 3);
+        // Any live cell with 0 or 1 live neighbors becomes dead, because of underpopulation
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Alive('X'),
+                NeighbourState {
+                    alive: 1,
+                    dead: 0,
+                    alive_b: 0
+                }
+            ),
+            CellState::Dead(' ')
+        );
+        //Any live cell with 2 or 3 live neighbors stays alive, because its neighborhood is just right
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Alive('X'),
+                NeighbourState {
+                    alive: 3,
+                    dead: 0,
+                    alive_b: 0
+                }
+            ),
+            CellState::Alive('X')
+        );
+        // Any live cell with more than 3 live neighbors becomes dead, because of overpopulation
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Alive('X'),
+                NeighbourState {
+                    alive: 5,
+                    dead: 1,
+                    alive_b: 0
+                }
+            ),
+            CellState::Dead(' ')
+        );
+        // Any dead cell with exactly 3 live neighbors becomes alive, by reproduction
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Dead(' '),
+                NeighbourState {
+                    alive: 3,
+                    dead: 0,
+                    alive_b: 0
+                }
+            ),
+            CellState::Alive('X')
+        );
+    }
+
+    #[test]
+    fn test_new_random_custom_glyphs() {
+        let g = Grid::new_random_custom_glyphs(3, 3, 'A', 'D');
+        assert_eq!(g.cells.len(), 9);
+        let unexpected_states: Vec<&CellState> = g
+            .cells
+            .iter()
+            .filter(|c| {
+                let state = c.to_string();
+                state != "A" && state != "D"
+            })
+            .collect();
+        assert_eq!(unexpected_states.len(), 0);
+    }
+
+    #[test]
+    fn test_ruleset_parse() {
+        assert_eq!(Ruleset::parse("B3/S23"), Ok(Ruleset::conway()));
+        assert_eq!(Ruleset::parse("b3/s32"), Ok(Ruleset::conway()));
+        let highlife = Ruleset::parse("B36/S23").unwrap();
+        assert!(highlife.is_born(6));
+        assert!(!highlife.survives(6));
+        assert_eq!(highlife.to_string(), "B36/S23");
+        assert_eq!("B/S".parse::<Ruleset>().unwrap().to_string(), "B/S");
+    }
+
+    #[test]
+    fn test_ruleset_parse_errors() {
+        assert_eq!(Ruleset::parse("B3S23"), Err(RulesetError::MissingSeparator));
+        assert_eq!(
+            Ruleset::parse("3/S23"),
+            Err(RulesetError::MissingPrefix('B'))
+        );
+        assert_eq!(
+            Ruleset::parse("B3/23"),
+            Err(RulesetError::MissingPrefix('S'))
+        );
+        assert_eq!(
+            Ruleset::parse("B3/S2x"),
+            Err(RulesetError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            Ruleset::parse("B9/S23"),
+            Err(RulesetError::CountOutOfRange(9))
+        );
+        assert_eq!(
+            Ruleset::parse("B3/S233"),
+            Err(RulesetError::DuplicateCount(3))
+        );
+    }
+
+    #[test]
+    fn test_ruleset_new() {
+        assert_eq!(Ruleset::new(&[3], &[2, 3]), Ok(Ruleset::conway()));
+        assert_eq!(Ruleset::new(&[], &[]).unwrap().to_string(), "B/S");
+        assert_eq!(
+            Ruleset::new(&[3], &[2, 9]),
+            Err(RulesetError::CountOutOfRange(9))
+        );
+        assert_eq!(
+            Ruleset::new(&[3, 6, 3], &[2, 3]),
+            Err(RulesetError::DuplicateCount(3))
+        );
+        let presets = [
+            (Ruleset::conway(), "B3/S23"),
+            (Ruleset::highlife(), "B36/S23"),
+            (Ruleset::day_and_night(), "B3678/S34678"),
+            (Ruleset::seeds(), "B2/S"),
+            (Ruleset::life_without_death(), "B3/S012345678"),
+        ];
+        for (preset, notation) in presets {
+            assert_eq!(Ruleset::parse(notation), Ok(preset));
+            assert_eq!(preset.to_string(), notation);
+        }
+    }
+
+    #[test]
+    fn test_get_cell_state_custom_ruleset() {
+        let mut g = Grid::new_empty(3, 3);
+        assert_eq!(g.ruleset(), None);
+        g.set_ruleset(Ruleset::parse("B36/S23").unwrap());
+        // HighLife births on 6 neighbours where Conway does not
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Dead(' '),
+                NeighbourState {
+                    alive: 6,
+                    dead: 2,
+                    alive_b: 0
+                }
+            ),
+            CellState::Alive('X')
+        );
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Alive('X'),
+                NeighbourState {
+                    alive: 2,
+                    dead: 6,
+                    alive_b: 0
+                }
+            ),
+            CellState::Alive('X')
+        );
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Alive('X'),
+                NeighbourState {
+                    alive: 4,
+                    dead: 4,
+                    alive_b: 0
+                }
+            ),
+            CellState::Dead(' ')
+        );
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        #[derive(Debug)]
+        struct AlwaysDead;
+        impl Rule for AlwaysDead {
+            fn next(&self, _current: CellState, _alive_neighbours: u32) -> CellState {
+                CellState::Dead(' ')
+            }
+        }
+        let mut g = Grid::new_random(5, 5);
+        g.set_rule(AlwaysDead);
+        assert_eq!(g.ruleset(), None);
+        g.update_states();
+        assert_eq!(g.population(), 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut grid = Grid::from_plaintext("O..\n.OO").unwrap();
+        grid.set_edge_mode(EdgeMode::Toroidal);
+        let bits: Grid<u8> = grid.map(|cell| u8::from(cell.is_alive()));
+        assert_eq!((bits.width, bits.height), (3, 2));
+        assert_eq!(bits.cells, [1, 0, 0, 0, 1, 1]);
+        assert_eq!(bits[Point::new(2, 1)], 1);
+        assert_eq!(bits.edge_mode(), EdgeMode::Toroidal);
+
+        let inverted = grid.map(|cell| match cell {
+            CellState::Dead(_) => CellState::Alive('X'),
+            _ => CellState::Dead(' '),
+        });
+        assert_eq!(inverted.to_string(), " XX\nX  \n");
+        assert_eq!(Grid::new_empty(0, 4).map(|_| 1u8).cells, []);
+    }
+
+    #[test]
+    fn test_custom_rule_with_dead_count() {
+        /// Dead cells come to life when they have 8 neighbours and all are dead
+        #[derive(Debug)]
+        struct Hermits;
+        impl Rule for Hermits {
+            fn next(&self, _current: CellState, _alive_neighbours: u32) -> CellState {
+                unreachable!("the grid calls next_with_counts")
+            }
+
+            fn next_with_counts(
+                &self,
+                current: CellState,
+                neighbours: NeighbourCounts,
+            ) -> CellState {
+                match (current.is_alive(), neighbours) {
+                    (false, NeighbourCounts { alive: 0, dead: 8 }) => CellState::Alive('X'),
+                    _ => CellState::Dead(' '),
+                }
+            }
+        }
+        // Only the middle cell has a full set of neighbours in a bounded grid
+        let mut g = Grid::new_empty(3, 3);
+        g.set_rule(Hermits);
+        g.update_states_sparse();
+        assert_eq!(g.to_string(), "   \n X \n   \n");
+        g.clear();
+        g.set_edge_mode(EdgeMode::Toroidal);
+        g.update_states();
+        assert_eq!(g.population(), 9);
+    }
+
+    #[test]
+    fn test_sparse_matches_dense_for_births_at_edges() {
+        /// Dead cells with no alive neighbours come to life on the edges of bounded grids
+        #[derive(Debug)]
+        struct EdgeBirths;
+        impl Rule for EdgeBirths {
+            fn next(&self, _current: CellState, _alive_neighbours: u32) -> CellState {
+                unreachable!("the grid calls next_with_counts")
+            }
+
+            fn next_with_counts(
+                &self,
+                current: CellState,
+                neighbours: NeighbourCounts,
+            ) -> CellState {
+                match (current.is_alive(), neighbours) {
+                    (false, NeighbourCounts { alive: 0, dead }) if dead < 8 => {
+                        CellState::Alive('X')
+                    }
+                    _ => CellState::Dead(' '),
+                }
+            }
+        }
+        let mut dense = Grid::new_empty(4, 3);
+        dense.set_rule(EdgeBirths);
+        let mut sparse = Grid::new_empty(4, 3);
+        sparse.set_rule(EdgeBirths);
+        for _ in 0..3 {
+            dense.update_states();
+            sparse.update_states_sparse();
+            assert_eq!(sparse.to_string(), dense.to_string());
+        }
+        assert_eq!(sparse.population(), dense.population());
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    /// Brian's Brain, where cells are on for one generation then dying for one
+    enum Brain {
+        On,
+        Dying,
+        Off,
+    }
+
+    impl Cell for Brain {
+        fn step(self, neighbours: &[Self]) -> Self {
+            match self {
+                Brain::On => Brain::Dying,
+                Brain::Dying => Brain::Off,
+                Brain::Off if neighbours.iter().filter(|&&n| n == Brain::On).count() == 2 => {
+                    Brain::On
+                }
+                Brain::Off => Brain::Off,
+            }
+        }
+    }
+
+    #[test]
+    fn test_brians_brain() {
+        let mut grid = Grid::new_filled(4, 4, Brain::Off);
+        grid[Point::new(1, 1)] = Brain::On;
+        grid[Point::new(1, 2)] = Brain::On;
+        grid.evolve();
+        let states = |grid: &Grid<Brain>, state| {
+            grid.cells
+                .iter()
+                .enumerate()
+                .filter(|&(_, &cell)| cell == state)
+                .map(|(idx, _)| grid.pos(idx))
+                .collect::<Vec<_>>()
+        };
+        let p = Point::new;
+        assert_eq!(states(&grid, Brain::Dying), [p(1, 1), p(1, 2)]);
+        assert_eq!(
+            states(&grid, Brain::On),
+            [p(0, 1), p(2, 1), p(0, 2), p(2, 2)]
+        );
+        grid.evolve();
+        assert_eq!(states(&grid, Brain::Dying).len(), 4);
+        assert_eq!(grid[Point::new(1, 1)], Brain::Off);
+    }
+
+    #[test]
+    fn test_cell_state_evolves_like_update_states() {
+        let mut grid = Grid::new_random(20, 15);
+        grid.set_edge_mode(EdgeMode::Toroidal);
+        let mut cells = Grid::new_filled(20, 15, CellState::Dead(' '));
+        cells.cells.clone_from(&grid.cells);
+        cells.set_edge_mode(EdgeMode::Toroidal);
+        for generation in 0..10 {
+            grid.update_states();
+            cells.evolve();
+            assert_eq!(cells.cells, grid.cells, "generation {generation}");
+        }
+    }
+
+    #[test]
+    fn test_grid_equality() {
+        let mut grid = Grid::from_plaintext(".O.\nO.O").unwrap();
+        let mut same = Grid::from_plaintext(".O.\nO.O").unwrap();
+        same.set_ruleset(Ruleset::parse("B36/S23").unwrap());
+        same.set_history_capacity(3);
+        assert_eq!(grid, same);
+        assert!(!grid.differs_from(&same));
+
+        grid.toggle_cell(Point::new(2, 0));
+        assert_ne!(grid, same);
+        assert!(grid.differs_from(&same));
+
+        // Same cells, laid out in different dimensions
+        let row = Grid::from_plaintext(".O.O.O").unwrap();
+        assert_eq!(row.cells, same.cells);
+        assert!(row.differs_from(&same));
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let mut grid = Grid::from_plaintext(".O.\n.O.\n.O.").unwrap();
+        grid.set_ruleset(Ruleset::parse("B36/S23").unwrap());
+        grid.set_history_capacity(2);
+        let mut clone = grid.clone();
+        assert_eq!(clone, grid);
+        assert_eq!(clone.ruleset(), grid.ruleset());
+        assert_eq!(clone.population(), 3);
+
+        clone.update_states();
+        clone.toggle_cell(Point::new(0, 0));
+        assert_ne!(clone, grid);
+        assert_eq!(grid.to_string(), " X \n X \n X \n");
+        assert_eq!(grid.population(), 3);
+        assert!(!grid.step_back());
+        assert!(clone.step_back());
+    }
+
+    #[test]
+    fn test_conway_rule_matches_ruleset() {
+        for alive_neighbours in 0..=MAX_NEIGHBOURS {
+            for current in [CellState::Alive('X'), CellState::Dead(' ')] {
+                assert_eq!(
+                    ConwayRule.next(current, alive_neighbours),
+                    Ruleset::conway().next(current, alive_neighbours),
+                    "{current:?} {alive_neighbours}"
+                );
+            }
+        }
+    }
+
+    fn glider(g: &mut Grid<CellState>) {
+        // x 0 x
+        // x x 0
+        // 0 0 0
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            let idx = g.idx(&Point::new(x, y));
+            g.cells[idx] = CellState::Alive(g.alive_glyph);
+        }
+    }
+
+    #[test]
+    fn test_get_neighbours_state_toroidal() {
+        let mut g = Grid::new_empty(3, 3);
+        g.set_edge_mode(EdgeMode::Toroidal);
+        g.cells[8] = CellState::Alive(g.alive_glyph);
+        // x x x
+        // x x x
+        // x x 0
+        let state = g.get_neighbours_state(Point { x: 0, y: 0 });
+        assert_eq!(state.dead, 7);
+        assert_eq!(state.alive, 1);
+    }
+
+    #[test]
+    fn test_glider_wraps_toroidal() {
+        let mut g = Grid::new_empty(6, 6);
+        g.set_edge_mode(EdgeMode::Toroidal);
+        glider(&mut g);
+        let start = g.cells.clone();
+        // A glider moves one cell diagonally every 4 generations, crossing both edges
+        // and re-entering from the opposite side to arrive back where it started
+        for _ in 0..4 * 6 {
+            g.update_states();
+            assert_eq!(g.population(), 5);
+        }
+        assert_eq!(g.cells, start);
+    }
+
+    #[test]
+    fn test_glider_expands_grid() {
+        let mut g = Grid::new_empty(10, 10);
+        g.set_edge_mode(EdgeMode::Expanding);
+        glider(&mut g);
+        for _ in 0..60 {
+            g.update_states();
+        }
+        // A glider bounded by the original edges would have died by now
+        assert_eq!(g.population(), 5);
+        assert!(g.width > 10 && g.height > 10);
+        // Starting in the top left corner, the grid first grew up and left too
+        let margin = EXPAND_MARGIN as Coord;
+        assert_eq!(g.origin(), Point::new(-margin, -margin));
+        // The glider keeps its shape
+        let (top_left, _) = g.live_bounds().unwrap();
+        assert_eq!(g.crop(top_left, 3, 3).alive_points().count(), 5);
+    }
+
+    #[test]
+    fn test_expanding_grows_up_and_left() {
+        let mut g = Grid::from_plaintext("OOO\n...\n...").unwrap();
+        g.set_edge_mode(EdgeMode::Expanding);
+        g.update_states();
+        // The bottom row is empty, so the grid does not grow down
+        assert_eq!(
+            (g.width, g.height),
+            (3 + 2 * EXPAND_MARGIN, 3 + EXPAND_MARGIN)
+        );
+        let margin = EXPAND_MARGIN as Coord;
+        assert_eq!(g.origin(), Point::new(-margin, -margin));
+        // The blinker flipped upright around its middle cell, which stayed in place
+        let middle = Point::new(1 + margin, margin);
+        assert!(g[middle].is_alive());
+        assert!(g[Point::new(middle.x, middle.y - 1)].is_alive());
+    }
+
+    #[test]
+    fn test_expanding_stops_at_limit() {
+        let side = 1 << 10;
+        assert_eq!(side * side, Grid::MAX_EXPANDED_CELLS);
+        let mut g = Grid::new_empty(side, side);
+        g.set_edge_mode(EdgeMode::Expanding);
+        g.set_cell(Point::new(0, 0), CellState::Alive('X'));
+        g.update_states();
+        assert_eq!((g.width, g.height), (side, side));
+    }
+
+    #[test]
+    fn test_glider_dies_bounded() {
+        let mut g = Grid::new_empty(6, 6);
+        glider(&mut g);
+        for _ in 0..4 * 6 {
+            g.update_states();
+        }
+        assert_ne!(g.population(), 5);
+    }
+
+    #[test]
+    fn test_toggle_cell() {
+        let mut g = Grid::new_empty(3, 3);
+        let p = Point::new(1, 2);
+        assert!(g.toggle_cell(p));
+        assert_eq!(g[p], CellState::Alive(g.alive_glyph));
+        assert_eq!(g.population(), 1);
+        assert!(g.toggle_cell(p));
+        assert_eq!(g[p], CellState::Dead(g.dead_glyph));
+        assert_eq!(g.population(), 0);
+    }
+
+    #[test]
+    fn test_population_stays_cached() {
+        let mut g = Grid::new_random_seeded(12, 9, 7);
+        g.set_decay(2);
+        g.set_history_capacity(4);
+        assert_eq!(g.population(), g.calculate_population());
+        let alive = CellState::Alive(g.alive_glyph);
+        let dead = CellState::Dead(g.dead_glyph);
+        for i in 0..40 {
+            let p = Point::new(i * 7 % 12, i * 5 % 9);
+            match i % 4 {
+                0 => g.set_cell(p, alive),
+                1 => g.set_cell(p, dead),
+                2 => g.set_cell(p, CellState::Dying(1)),
+                _ => g.toggle_cell(p),
+            };
+            assert_eq!(g.population(), g.calculate_population(), "edit {i}");
+        }
+        g.update_states();
+        assert_eq!(g.population(), g.calculate_population());
+        g.update_states_sparse();
+        assert_eq!(g.population(), g.calculate_population());
+        g.step_back();
+        assert_eq!(g.population(), g.calculate_population());
+        g.rotate_cw();
+        assert_eq!(g.population(), g.calculate_population());
+        g.resize(5, 14);
+        assert_eq!(g.population(), g.calculate_population());
+        g.randomize(0.3);
+        assert_eq!(g.population(), g.calculate_population());
+        g.insert(&patterns::glider(), Point::new(1, 1));
+        assert_eq!(g.population(), g.calculate_population());
+        g.clear();
+        assert_eq!(g.population(), 0);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let mut g = Grid::from_plaintext("OO.\nO..").unwrap();
+        let expected = SoupResult {
+            generations: 2,
+            final_population: 4,
+            stabilized: true,
+        };
+        assert_eq!(g.run_until_stable(100), expected);
+
+        let mut g = Grid::new_empty(8, 8);
+        g.set_edge_mode(EdgeMode::Toroidal);
+        g.insert(&patterns::glider(), Point::new(0, 0));
+        let expected = SoupResult {
+            generations: 20,
+            final_population: 5,
+            stabilized: false,
+        };
+        assert_eq!(g.run_until_stable(20), expected);
+    }
+
+    #[test]
+    fn test_two_color_birth_takes_majority() {
+        for (parents, born) in [
+            ([true, true, false], CellState::AliveB('X')),
+            ([false, true, false], CellState::Alive('X')),
+        ] {
+            let mut g = Grid::new_empty(3, 3);
+            for (x, color_b) in parents.into_iter().enumerate() {
+                let alive = if color_b {
+                    CellState::AliveB(g.alive_glyph)
+                } else {
+                    CellState::Alive(g.alive_glyph)
+                };
+                g.set_cell(Point::new(x as i32, 0), alive);
+            }
+            let middle = g[Point::new(1, 0)];
+            g.update_states();
+            assert_eq!(g[Point::new(1, 1)], born, "{parents:?}");
+            assert_eq!(g[Point::new(1, 0)], middle, "{parents:?}");
+        }
+    }
+
+    #[test]
+    fn test_two_color_cells_keep_color() {
+        let mut g = Grid::from_plaintext("OO\nOO").unwrap();
+        g.set_cell(Point::new(0, 0), CellState::AliveB(g.alive_glyph));
+        g.update_states();
+        assert_eq!(g[Point::new(0, 0)], CellState::AliveB('X'));
+        assert_eq!(g[Point::new(1, 1)], CellState::Alive('X'));
+        assert_eq!(g.population(), 4);
+
+        let g = Grid::new_random_two_color(20, 20);
+        assert_eq!(g.population(), g.calculate_population());
+        let mut sparse = Grid::new_empty(20, 20);
+        sparse.insert(&g, Point::new(0, 0));
+        assert_eq!(sparse.cells, g.cells);
+    }
+
+    #[test]
+    fn test_sprinkle() {
+        let mut g = Grid::new_empty(100, 100);
+        g.sprinkle_with_rng(0.1, &mut fastrand::Rng::with_seed(3));
+        assert!((900..=1100).contains(&g.population()), "{}", g.population());
+        assert_eq!(g.population(), g.calculate_population());
+
+        let mut g = Grid::from_plaintext("O.").unwrap();
+        g.set_cell(Point::new(1, 0), CellState::Dying(1));
+        g.sprinkle(1.0);
+        assert_eq!(g.cells, [CellState::Alive('X'), CellState::Dying(1)]);
+        g.sprinkle(-1.0);
+        assert_eq!(g.population(), 1);
+    }
+
+    #[test]
+    fn test_fill_region() {
+        let alive = CellState::Alive('X');
+        let mut g = Grid::new_empty(4, 3);
+        g.fill_region(Point::new(1, 0), Point::new(2, 1), alive);
+        assert_eq!(g.to_string(), " XX \n XX \n    \n");
+        g.fill_region(Point::new(2, 1), Point::new(1, 0), CellState::Dead(' '));
+        assert_eq!(g.population(), 0);
+
+        g.fill_region(Point::new(3, -5), Point::new(10, 1), alive);
+        assert_eq!(g.to_string(), "   X\n   X\n    \n");
+        g.fill_region(Point::new(-1, -1), Point::new(-3, 8), alive);
+        g.fill_region(Point::new(4, 3), Point::new(9, 9), alive);
+        assert_eq!(g.population(), 2);
+        g.fill_region(Point::new(-9, -9), Point::new(9, 9), alive);
+        assert_eq!(g.population(), 12);
+        assert_eq!(g.population(), g.calculate_population());
+
+        Grid::new_empty(0, 0).fill_region(Point::new(0, 0), Point::new(1, 1), alive);
+    }
+
+    #[test]
+    fn test_set_cell_out_of_bounds() {
+        let mut g = Grid::new_empty(3, 3);
+        for p in [
+            Point::new(-1, 0),
+            Point::new(0, -1),
+            Point::new(3, 0),
+            Point::new(0, 3),
+        ] {
+            assert!(!g.set_cell(p, CellState::Alive(g.alive_glyph)));
+            assert!(!g.toggle_cell(p));
+        }
+        assert_eq!(g.population(), 0);
+        assert!(g.cells.iter().all(|c| *c == CellState::Dead(' ')));
+    }
+
+    #[test]
+    fn test_update_states_matches_fresh_allocation() {
+        let mut g = Grid::new_random(12, 9);
+        for _ in 0..10 {
+            let expected: Vec<CellState> = g
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(idx, cell)| g.get_cell_state(cell, g.get_neighbours_state(g.pos(idx))))
+                .collect();
+            g.update_states();
+            assert_eq!(g.cells, expected);
+        }
+    }
+
+    #[test]
+    fn test_update_states_reuses_buffers() {
+        let mut g = Grid::new_random(12, 9);
+        g.update_states();
+        g.update_states();
+        let buffers = [g.cells.as_ptr(), g.previous.as_ptr(), g.older.as_ptr()];
+        for _ in 0..10 {
+            g.update_states();
+            assert!(buffers.contains(&g.cells.as_ptr()));
+            assert!(buffers.contains(&g.previous.as_ptr()));
+            assert!(buffers.contains(&g.older.as_ptr()));
+        }
+    }
+
+    #[test]
+    fn test_count_neighbours_matches_get_neighbours_state() {
+        for edge_mode in [EdgeMode::Bounded, EdgeMode::Toroidal] {
+            for neighbourhood in [Neighbourhood::Moore, Neighbourhood::VonNeumann] {
+                let mut g = Grid::new_random(7, 5);
+                g.set_edge_mode(edge_mode);
+                g.set_neighbourhood(neighbourhood);
+                for idx in 0..g.cells.len() {
+                    assert_eq!(
+                        g.count_neighbours(idx),
+                        g.get_neighbours_state(g.pos(idx)),
+                        "{edge_mode:?} {neighbourhood:?} {:?}",
+                        g.pos(idx)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_von_neumann_ignores_diagonals() {
+        let mut g = Grid::new_empty(3, 3);
+        for p in [(0, 0), (2, 0), (0, 2), (2, 2)] {
+            g.set_cell(Point::new(p.0, p.1), CellState::Alive(g.alive_glyph));
+        }
+        assert_eq!(g.count_neighbours(4).alive, 4);
+        g.set_neighbourhood(Neighbourhood::VonNeumann);
+        assert_eq!(g.count_neighbours(4).alive, 0);
+        assert_eq!(g.get_neighbours_state(Point::new(1, 1)).alive, 0);
+    }
+
+    #[test]
+    fn test_count_neighbours_known_pattern() {
+        let mut g = Grid::new_empty(5, 5);
+        glider(&mut g);
+        // x 0 x x x
+        // x x 0 x x
+        // 0 0 0 x x
+        let expected = [(1, 1, 5), (2, 2, 2), (0, 0, 1), (3, 1, 2), (1, 3, 3)];
+        for (x, y, alive) in expected {
+            let idx = g.idx(&Point::new(x, y));
+            assert_eq!(g.count_neighbours(idx).alive, alive, "{x}, {y}");
+        }
+    }
+
+    #[test]
+    fn test_decay_fades_dying_cells() {
+        let mut g = Grid::new_empty(3, 3);
+        g.set_decay(2);
+        g.cells[4] = CellState::Alive(g.alive_glyph);
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Dying(2));
+        assert_eq!(g.population(), 0);
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Dying(1));
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Dead(g.dead_glyph));
+    }
+
+    #[test]
+    fn test_decay_dying_cells_are_not_alive() {
+        let mut g = Grid::new_empty(3, 3);
+        g.set_decay(2);
+        g.cells[0] = CellState::Dying(2);
+        g.cells[1] = CellState::Alive(g.alive_glyph);
+        g.cells[2] = CellState::Alive(g.alive_glyph);
+        let state = g.get_neighbours_state(Point::new(1, 1));
+        assert_eq!(state.alive, 2);
+        assert_eq!(state.dead, 6);
+        // A dying cell is not born again, even with three alive neighbours
+        assert_eq!(
+            g.get_cell_state(
+                &CellState::Dying(2),
+                NeighbourState {
+                    alive: 3,
+                    dead: 5,
+                    alive_b: 0
+                }
+            ),
+            CellState::Dying(1)
+        );
+    }
+
+    #[test]
+    fn test_dying_display() {
+        let mut g = Grid::new_empty(3, 1);
+        g.cells = vec![
+            CellState::Dying(3),
+            CellState::Dying(2),
+            CellState::Dying(1),
+        ];
+        assert_eq!(g.to_string(), "▓▒░\n");
+    }
+
+    #[test]
+    fn test_age_tracking() {
+        let mut g = Grid::new_empty(4, 4);
+        assert_eq!(g.ages(), None);
+        g.set_age_tracking(true);
+        // A block is a still life, so it keeps ageing
+        for p in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            g.set_cell(Point::new(p.0, p.1), CellState::Alive(g.alive_glyph));
+        }
+        g.update_states();
+        g.update_states();
+        let block = g.idx(&Point::new(1, 1));
+        assert_eq!(g.ages().unwrap()[block], 2);
+        assert_eq!(g.ages().unwrap()[0], 0);
+        // Killing a cell resets its age, and the rest of the block dies out around it
+        g.set_cell(Point::new(2, 2), CellState::Dead(g.dead_glyph));
+        assert_eq!(g.ages().unwrap()[g.idx(&Point::new(2, 2))], 0);
+        g.update_states();
+        assert_eq!(g.ages().unwrap()[block], 3);
+        assert_eq!(g.ages().unwrap()[g.idx(&Point::new(2, 2))], 0);
+
+        g.set_age_tracking(false);
+        assert_eq!(g.ages(), None);
+    }
+
+    #[test]
+    fn test_heat_tracking() {
+        let mut g = Grid::from_plaintext("....\n.OO.\n.OO.\n....").unwrap();
+        assert_eq!(g.heat(), None);
+        g.set_heat_tracking(true);
+        g.set_heat_decay(0.5);
+        let block = g.idx(&Point::new(1, 1));
+        for expected in [1.0, 1.5, 1.75] {
+            g.update_states();
+            assert_eq!(g.heat().unwrap()[block], expected);
+            assert_eq!(g.heat().unwrap()[0], 0.0);
+        }
+        g.clear();
+        assert!(g.heat().unwrap().iter().all(|&heat| heat == 0.0));
+
+        // Heat fades once the cell dies
+        g.set_cell(Point::new(0, 0), CellState::Alive('X'));
+        g.update_states();
+        g.heat.as_mut().unwrap()[0] = 4.0;
+        g.update_states();
+        assert_eq!(g.heat().unwrap()[0], 2.0);
+
+        g.resize(2, 3);
+        assert_eq!(g.heat().unwrap(), [0.0; 6]);
+        g.set_heat_decay(2.0);
+        assert_eq!(g.heat_decay(), 0.99);
+    }
+
+    #[test]
+    fn test_birth_parity() {
+        let mut g = Grid::from_plaintext(".....\n..O..\n..O..\n..O..\n.....").unwrap();
+        assert_eq!(g.birth_parity(), None);
+        g.set_birth_parity_tracking(true);
+        let (top, middle, left) = (
+            g.idx(&Point::new(2, 1)),
+            g.idx(&Point::new(2, 2)),
+            g.idx(&Point::new(1, 2)),
+        );
+        // Generation 1 is odd: the blinker's new arms are born in it, its centre survives
+        g.update_states();
+        let parity = g.birth_parity().unwrap();
+        assert_eq!(
+            (parity[left], parity[middle], parity[top]),
+            (true, false, false)
+        );
+
+        // Generation 2 is even, and the sparse update keeps the bits the same way
+        g.update_states_sparse();
+        let parity = g.birth_parity().unwrap();
+        assert_eq!(
+            (parity[left], parity[middle], parity[top]),
+            (false, false, false)
+        );
+        g.update_states_sparse();
+        assert!(g.birth_parity().unwrap()[left]);
+
+        g.flip_horizontal();
+        assert!(g.birth_parity().unwrap()[g.idx(&Point::new(3, 2))]);
+        g.resize(4, 4);
+        assert_eq!(g.birth_parity().unwrap().len(), 16);
+        assert!(g.birth_parity().unwrap()[g.idx(&Point::new(3, 2))]);
+        g.clear();
+        assert!(g.birth_parity().unwrap().iter().all(|&odd| !odd));
+    }
+
+    fn filled(width: usize, height: usize) -> Grid<CellState> {
+        let mut g = Grid::new_empty(width, height);
+        for idx in 0..g.cells.len() {
+            g.set_cell(g.pos(idx), CellState::Alive(g.alive_glyph));
+        }
+        g
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut g = Grid::new_empty(5, 5);
+        g.insert(&filled(2, 2), Point::new(1, 2));
+        assert_eq!(g.to_string(), "     \n     \n XX  \n XX  \n     \n");
+        assert_eq!(g.population(), 4);
+    }
+
+    #[test]
+    fn test_insert_keeps_alive_cells() {
+        let mut g = Grid::new_empty(3, 1);
+        g.set_cell(Point::new(0, 0), CellState::Alive(g.alive_glyph));
+        let pattern = Grid::from_plaintext(".O").unwrap();
+        g.insert(&pattern, Point::new(0, 0));
+        assert_eq!(g.to_string(), "XX \n");
+        assert_eq!(g.population(), 2);
+    }
+
+    #[test]
+    fn test_insert_clips_edges() {
+        let pattern = filled(3, 3);
+        for (at, expected) in [
+            (Point::new(-2, 1), "    \nX   \nX   \nX   \n"),
+            (Point::new(3, 0), "   X\n   X\n   X\n    \n"),
+            (Point::new(1, -2), " XXX\n    \n    \n    \n"),
+            (Point::new(0, 3), "    \n    \n    \nXXX \n"),
+        ] {
+            let mut g = Grid::new_empty(4, 4);
+            g.insert(&pattern, at);
+            assert_eq!(g.to_string(), expected, "{at:?}");
+        }
+    }
+
+    #[test]
+    fn test_insert_larger_pattern() {
+        let mut g = Grid::new_empty(2, 2);
+        g.insert(&filled(5, 5), Point::new(-1, -1));
+        assert_eq!(g.population(), 4);
+        g.insert(&filled(5, 5), Point::new(10, 10));
+        assert_eq!(g.population(), 4);
+    }
+
+    #[test]
+    fn test_new_random_seeded() {
+        let g = Grid::new_random_seeded(20, 10, 12345);
+        assert_eq!(g.cells, Grid::new_random_seeded(20, 10, 12345).cells);
+        assert_ne!(g.cells, Grid::new_random_seeded(20, 10, 54321).cells);
+        assert_eq!(g.population(), g.calculate_population());
+    }
+
+    #[test]
+    fn test_random_with_rng_is_reproducible() {
+        let build = |seed| {
+            let mut rng = fastrand::Rng::with_seed(seed);
+            let mut g = Grid::new_random_with_rng(30, 20, 0.4, &mut rng);
+            g.update_states();
+            g.randomize_with_rng(0.2, &mut rng);
+            g.sprinkle_with_rng(0.1, &mut rng);
+            g
+        };
+        assert_eq!(build(1).cells, build(1).cells);
+        assert_ne!(build(1).cells, build(2).cells);
+    }
+
+    #[test]
+    fn test_alive_points() {
+        let mut g = Grid::new_empty(4, 3);
+        g.set_cell(Point::new(3, 0), CellState::Alive(g.alive_glyph));
+        g.set_cell(Point::new(1, 2), CellState::Alive(g.alive_glyph));
+        let points: Vec<Point> = g.alive_points().collect();
+        assert_eq!(points, [Point::new(3, 0), Point::new(1, 2)]);
+        assert_eq!(Grid::new_empty(4, 3).alive_points().count(), 0);
+    }
+
+    #[test]
+    fn test_live_bounds() {
+        let mut g = Grid::new_empty(5, 4);
+        assert_eq!(g.live_bounds(), None);
+        g.set_cell(Point::new(3, 1), CellState::Alive(g.alive_glyph));
+        assert_eq!(g.live_bounds(), Some((Point::new(3, 1), Point::new(3, 1))));
+        g.set_cell(Point::new(4, 0), CellState::Alive(g.alive_glyph));
+        g.set_cell(Point::new(0, 3), CellState::Alive(g.alive_glyph));
+        assert_eq!(g.live_bounds(), Some((Point::new(0, 0), Point::new(4, 3))));
+    }
+
+    #[test]
+    fn test_crop() {
+        let mut g = Grid::new_empty(4, 4);
+        glider(&mut g);
+        g.population = g.calculate_population();
+        let full = g.crop(Point::new(0, 0), 4, 4);
+        assert_eq!(full.cells, g.cells);
+        assert_eq!(full.population(), 5);
+
+        // Only the bottom right cell of the glider lies in the region inside the grid
+        let clipped = g.crop(Point::new(2, 2), 3, 3);
+        assert_eq!((clipped.width, clipped.height), (3, 3));
+        assert_eq!(clipped.population(), 1);
+        assert!(clipped.cells[0].is_alive());
+
+        let outside = g.crop(Point::new(-5, -5), 2, 2);
+        assert_eq!(outside.population(), 0);
+
+        // Oversized regions are shrunk to fit the cap
+        let huge = g.crop(Point::new(0, 0), usize::MAX, 2);
+        assert_eq!((huge.width, huge.height), (Grid::MAX_CELLS, 1));
+        assert_eq!(huge.cells.len(), Grid::MAX_CELLS);
+        // Only the top row of the glider is left
+        assert_eq!(huge.population(), 1);
+    }
+
+    #[test]
+    fn test_transforms_compose() {
+        let r_pentomino = Grid::from_plaintext(".OO\nOO.\n.O.").unwrap();
+        let mut g = r_pentomino.crop(Point::new(0, 0), 3, 4);
+        let original = g.cells.clone();
+
+        g.rotate_cw();
+        assert_eq!((g.width, g.height), (4, 3));
+        assert_eq!(g.to_string(), "  X \n XXX\n   X\n");
+        for _ in 0..3 {
+            g.rotate_cw();
+        }
+        assert_eq!((g.width, g.height), (3, 4));
+        assert_eq!(g.cells, original);
+
+        g.rotate_cw();
+        g.rotate_ccw();
+        assert_eq!(g.cells, original);
+
+        g.flip_horizontal();
+        assert_eq!(g.to_string(), "XX \n XX\n X \n   \n");
+        g.flip_horizontal();
+        assert_eq!(g.cells, original);
+
+        // Two quarter turns are the same as flipping both ways
+        let mut turned = r_pentomino.crop(Point::new(0, 0), 3, 4);
+        turned.rotate_cw();
+        turned.rotate_cw();
+        g.flip_horizontal();
+        g.flip_vertical();
+        assert_eq!(g.cells, turned.cells);
+        assert_eq!(g.population(), 5);
+    }
+
+    #[test]
+    fn test_update_states_sparse_matches_full_scan() {
+        for edge_mode in [EdgeMode::Bounded, EdgeMode::Toroidal] {
+            for decay in [0, 2] {
+                let mut full = Grid::new_random_density(23, 17, 0.2);
+                full.set_edge_mode(edge_mode);
+                full.set_decay(decay);
+                full.set_age_tracking(true);
+                let mut sparse = full.crop(Point::new(0, 0), 23, 17);
+                sparse.set_edge_mode(edge_mode);
+                sparse.set_decay(decay);
+                sparse.set_age_tracking(true);
+                for generation in 0..50 {
+                    full.update_states();
+                    sparse.update_states_sparse();
+                    let context = format!("{edge_mode:?} decay {decay} generation {generation}");
+                    assert_eq!(sparse.cells, full.cells, "{context}");
+                    assert_eq!(sparse.ages(), full.ages(), "{context}");
+                    assert_eq!(sparse.population(), full.population(), "{context}");
+                    assert_eq!(sparse.status(), full.status(), "{context}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_states_sparse_births_from_nothing() {
+        let mut g = Grid::new_empty(3, 3);
+        g.set_ruleset(Ruleset::parse("B0/S").unwrap());
+        g.update_states_sparse();
+        assert_eq!(g.population(), 9);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = History {
+            capacity: 2,
+            ..Default::default()
+        };
+        for generation in 0..4 {
+            history.push(&[CellState::Dying(generation)], None);
+        }
+        let kept: Vec<Vec<CellState>> = history.states.iter().map(|s| s.0.clone()).collect();
+        assert_eq!(kept, [vec![CellState::Dying(2)], vec![CellState::Dying(3)]]);
+        history.capacity = 0;
+        history.push(&[], None);
+        assert_eq!(history.states.len(), 2);
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut g = Grid::new_random(8, 8);
+        g.set_age_tracking(true);
+        g.set_history_capacity(3);
+        let mut generations = vec![(g.cells.clone(), g.ages().unwrap().to_vec())];
+        for _ in 0..5 {
+            g.update_states();
+            generations.push((g.cells.clone(), g.ages().unwrap().to_vec()));
+        }
+        generations.pop();
+        for _ in 0..3 {
+            assert!(g.step_back());
+            let (cells, ages) = generations.pop().unwrap();
+            assert_eq!(g.cells, cells);
+            assert_eq!(g.ages().unwrap(), ages);
+            assert_eq!(g.population(), g.calculate_population());
+        }
+        assert!(!g.step_back());
+
+        g.update_states();
+        g.clear();
+        assert!(!g.step_back());
+    }
+
+    #[test]
+    fn test_centered_in() {
+        // A 2x3 pattern away from the origin of its grid
+        let mut g = Grid::new_empty(6, 6);
+        for (x, y) in [(3, 2), (4, 2), (3, 3), (4, 4)] {
+            g.set_cell(Point::new(x, y), CellState::Alive(g.alive_glyph));
+        }
+        let centered = g.centered_in(5, 6);
+        assert_eq!((centered.width, centered.height), (5, 6));
+        // The odd spare column and row go to the right and bottom
+        assert_eq!(
+            centered.live_bounds(),
+            Some((Point::new(1, 1), Point::new(2, 3)))
+        );
+        assert_eq!(centered.population(), 4);
+
+        // Clipped evenly to the middle row
+        let clipped = g.centered_in(2, 1);
+        assert_eq!(clipped.to_string(), "X \n");
+
+        assert_eq!(Grid::new_empty(2, 2).centered_in(3, 3).population(), 0);
+
+        let huge = g.centered_in(usize::MAX, usize::MAX);
+        assert!(huge.cells.len() <= Grid::MAX_CELLS);
+        assert_eq!(huge.population(), 4);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut g = Grid::new_random_density(5, 3, 1.0);
+        g.set_decay(2);
+        g.set_age_tracking(true);
+        g.update_states();
+        g.clear();
+        assert_eq!((g.width, g.height, g.cells.len()), (5, 3, 15));
+        assert!(g.cells.iter().all(|c| *c == CellState::Dead(g.dead_glyph)));
+        assert!(g.ages().unwrap().iter().all(|&age| age == 0));
+        assert_eq!(g.population(), 0);
+        assert_eq!(g.status(), GridStatus::Evolving);
+    }
+
+    #[test]
+    fn test_randomize() {
+        let mut g = Grid::new_empty(4, 6);
+        g.randomize(0.5);
+        assert_eq!((g.width, g.height, g.cells.len()), (4, 6, 24));
+        assert_eq!(g.population(), g.calculate_population());
+        g.randomize(1.0);
+        assert_eq!(g.population(), 24);
+        g.randomize(0.0);
+        assert_eq!(g.population(), 0);
+    }
+
+    #[test]
+    fn test_new_random_symmetric() {
+        let symmetries = [
+            Symmetry::Horizontal,
+            Symmetry::Vertical,
+            Symmetry::Both,
+            Symmetry::Rotational,
+        ];
+        for symmetry in symmetries {
+            let grid = Grid::new_random_symmetric(10, 10, symmetry, 0.5);
+            for y in 0..10 {
+                for x in 0..10 {
+                    let mirror = match symmetry {
+                        Symmetry::Horizontal => Point::new(9 - x, y),
+                        Symmetry::Vertical => Point::new(x, 9 - y),
+                        Symmetry::Both => Point::new(9 - x, 9 - y),
+                        Symmetry::Rotational => Point::new(9 - y, x),
+                    };
+                    assert_eq!(
+                        grid[Point::new(x, y)],
+                        grid[mirror],
+                        "{symmetry:?} at ({x}, {y})"
+                    );
+                }
+            }
+        }
+        let both = Grid::new_random_symmetric(7, 5, Symmetry::Both, 0.5);
+        assert_eq!(both[Point::new(1, 1)], both[Point::new(5, 1)]);
+        assert_eq!(both[Point::new(1, 1)], both[Point::new(1, 3)]);
+        let rotational = Grid::new_random_symmetric(7, 5, Symmetry::Rotational, 0.5);
+        assert_eq!(rotational[Point::new(0, 1)], rotational[Point::new(6, 3)]);
+        assert_eq!(
+            Grid::new_random_symmetric(4, 4, Symmetry::Both, 1.0).population(),
+            16
+        );
+        assert_eq!(
+            Grid::new_random_symmetric(0, 0, Symmetry::Rotational, 1.0).population(),
+            0
+        );
+
+        // Scaled down to fit `MAX_CELLS`, the soup is symmetric in the size it got
+        let capped = Grid::new_random_symmetric(4000, 3000, Symmetry::Both, 0.5);
+        let (width, height) = (capped.width as i32, capped.height as i32);
+        assert_eq!((width, height), (1182, 886));
+        for y in 0..height {
+            for x in 0..width {
+                let mirror = Point::new(width - 1 - x, height - 1 - y);
+                assert_eq!(capped[Point::new(x, y)], capped[mirror], "at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_seeded_soup() {
+        let mut grid = Grid::new_random_seeded(32, 32, 2024);
+        let mut naive = grid.clone();
+        for _ in 0..100 {
+            naive.update_states();
+        }
+        let population = grid.run(100);
+        assert_eq!(population, naive.population());
+        assert_eq!(grid.cells, naive.cells);
+        // Pinned, so any change to how the soup evolves is noticed
+        assert_eq!(population, 80);
+        assert_eq!(grid.run(0), population);
+    }
+
+    #[test]
+    fn test_size_is_capped() {
+        let grid = Grid::new_random(4000, 3000);
+        assert!(grid.cells.len() <= Grid::MAX_CELLS);
+        assert_eq!(grid.cells.len(), grid.width * grid.height);
+        // The shape is kept as far as the cap allows
+        assert_eq!((grid.width, grid.height), (1182, 886));
+
+        let grid = Grid::new_empty(usize::MAX, usize::MAX);
+        assert!(grid.cells.len() <= Grid::MAX_CELLS);
+        assert_eq!(grid.cells.len(), grid.width * grid.height);
+        let grid = Grid::new_filled(5000, 5000, CellState::Dead(' '));
+        assert_eq!((grid.width, grid.height), (1024, 1024));
+
+        let mut grid = Grid::new_empty(2, 2);
+        grid.resize(usize::MAX, 2);
+        assert_eq!((grid.width, grid.height), (Grid::MAX_CELLS, 1));
+        assert_eq!(Grid::capped_size(7, 5, 0), (0, 1));
+        assert_eq!(Grid::capped_size(10, 10, 99), (9, 9));
+    }
+
+    #[test]
+    fn test_new_random_density() {
+        let empty = Grid::new_random_density(6, 4, 0.0);
+        assert!(empty.cells.iter().all(|c| *c == CellState::Dead(' ')));
+        assert_eq!(empty.population(), 0);
+        let full = Grid::new_random_density(6, 4, 1.0);
+        assert!(full.cells.iter().all(|c| *c == CellState::Alive('X')));
+        assert_eq!(full.population(), 24);
+        assert_eq!(Grid::new_random_density(6, 4, -1.0).population(), 0);
+        assert_eq!(Grid::new_random_density(6, 4, 2.0).population(), 24);
+    }
+
+    #[test]
+    fn test_status_block_is_still() {
+        let mut g = Grid::new_empty(4, 4);
+        g.insert(&filled(2, 2), Point::new(1, 1));
+        assert_eq!(g.status(), GridStatus::Evolving);
+        g.update_states();
+        assert_eq!(g.status(), GridStatus::Still);
+        g.toggle_cell(Point::new(0, 0));
+        assert_eq!(g.status(), GridStatus::Evolving);
+    }
+
+    #[test]
+    fn test_status_blinker_oscillates() {
+        let mut g = Grid::new_empty(5, 5);
+        g.insert(&filled(3, 1), Point::new(1, 2));
+        g.update_states();
+        assert_eq!(g.status(), GridStatus::Evolving);
+        for _ in 0..4 {
+            g.update_states();
+            assert_eq!(g.status(), GridStatus::Oscillating);
+        }
+    }
+
+    #[test]
+    fn test_status_glider_evolves() {
+        let mut g = Grid::new_empty(8, 8);
+        glider(&mut g);
+        for _ in 0..8 {
+            g.update_states();
+            assert_eq!(g.status(), GridStatus::Evolving);
+        }
+    }
+
+    #[test]
+    fn test_detect_period() {
+        let mut block = Grid::new_empty(4, 4);
+        block.insert(&filled(2, 2), Point::new(1, 1));
+        assert_eq!(block.detect_period(10), Some(1));
+
+        let mut blinker = Grid::new_empty(5, 5);
+        blinker.insert(&filled(3, 1), Point::new(1, 2));
+        assert_eq!(blinker.detect_period(10), Some(2));
+
+        // A glider on a torus returns to where it started after 4 generations per cell
+        let mut glider_grid = Grid::new_empty(6, 6);
+        glider_grid.set_edge_mode(EdgeMode::Toroidal);
+        glider(&mut glider_grid);
+        assert_eq!(glider_grid.detect_period(10), None);
+        assert_eq!(glider_grid.detect_period(30), Some(24));
+    }
+
+    #[test]
+    fn test_detect_period_after_transient() {
+        // A row of four cells takes a couple of generations to settle into a beehive
+        let mut g = Grid::new_empty(9, 9);
+        g.insert(&filled(4, 1), Point::new(2, 4));
+        assert_eq!(g.detect_period(20), Some(1));
+        assert_eq!(g.population(), 6);
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut g = Grid::new_empty(3, 3);
+        g[Point::new(2, 1)] = CellState::Alive('X');
+        assert_eq!(g.cells[5], CellState::Alive('X'));
+        assert_eq!(g[Point::new(2, 1)], CellState::Alive('X'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_out_of_bounds() {
+        let mut g = Grid::new_empty(3, 3);
+        g[Point::new(1, 3)] = CellState::Alive('X');
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_past_end_of_row() {
+        let mut g = Grid::new_empty(3, 3);
+        g[Point::new(3, 0)] = CellState::Alive('X');
+    }
+
+    #[test]
+    fn test_try_get_mut() {
+        let mut g = Grid::new_empty(3, 3);
+        let p = Point::new(0, 2);
+        *g.try_get_mut(p).unwrap() = CellState::Alive('X');
+        assert_eq!(g.try_get(p), Some(&CellState::Alive('X')));
+        for p in [Point::new(3, 0), Point::new(0, 3), Point::new(-1, -1)] {
+            assert!(g.try_get_mut(p).is_none());
+        }
+    }
+
+    #[test]
+    fn test_resize_grow() {
+        let mut g = Grid::from_plaintext("O.\n.O").unwrap();
+        g.set_age_tracking(true);
+        g.resize(3, 4);
+        assert_eq!((g.width, g.height), (3, 4));
+        assert_eq!(g.to_string(), "X  \n X \n   \n   \n");
+        assert_eq!(g.population(), 2);
+        assert_eq!(g.ages().unwrap().len(), 12);
+        assert_eq!(g.status(), GridStatus::Evolving);
+    }
+
+    #[test]
+    fn test_resize_shrink() {
+        let mut g = Grid::from_plaintext("O.O\n.OO\nOOO").unwrap();
+        g.update_states();
+        g.resize(2, 2);
+        assert_eq!(g.cells.len(), 4);
+        assert_eq!(g.population(), g.calculate_population());
+        let mut expected = Grid::from_plaintext("O.O\n.OO\nOOO").unwrap();
+        expected.update_states();
+        assert_eq!(
+            g.cells,
+            [0, 1, 3, 4].map(|idx| expected.cells[idx]).to_vec()
+        );
+        g.resize(0, 0);
+        assert!(g.cells.is_empty());
+        assert_eq!(g.population(), 0);
+    }
+}
@@ -0,0 +1,143 @@
+//! Well known patterns, each in the smallest grid that holds it.
+//!
+//! See: <https://conwaylife.com/wiki/>
+//!
+//! ```
+//! use game_of_life::{patterns, Grid, Point};
+//! let mut grid = Grid::new_empty(40, 20);
+//! grid.insert(&patterns::gosper_glider_gun(), Point::new(2, 2));
+//! ```
+
+use super::{CellState, Grid};
+
+fn plaintext(pattern: &str) -> Grid<CellState> {
+    Grid::from_plaintext(pattern).expect("built in patterns are valid plaintext")
+}
+
+/// The smallest spaceship, travelling diagonally down and right.
+pub fn glider() -> Grid<CellState> {
+    plaintext(".O.\n..O\nOOO")
+}
+
+/// A period 2 oscillator flipping between a horizontal and a vertical line.
+pub fn blinker() -> Grid<CellState> {
+    plaintext("OOO")
+}
+
+/// The most common still life.
+pub fn block() -> Grid<CellState> {
+    plaintext("OO\nOO")
+}
+
+/// A period 2 oscillator.
+pub fn toad() -> Grid<CellState> {
+    plaintext(".OOO\nOOO.")
+}
+
+/// A period 2 oscillator made of two blocks touching at a corner.
+pub fn beacon() -> Grid<CellState> {
+    plaintext("OO..\nOO..\n..OO\n..OO")
+}
+
+/// A period 3 oscillator.
+pub fn pulsar() -> Grid<CellState> {
+    plaintext(
+        "\
+..OOO...OOO..
+.............
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO..
+.............
+..OOO...OOO..
+O....O.O....O
+O....O.O....O
+O....O.O....O
+.............
+..OOO...OOO..",
+    )
+}
+
+/// A gun firing a new glider down and right every 30 generations.
+pub fn gosper_glider_gun() -> Grid<CellState> {
+    Grid::from_rle(
+        "x = 36, y = 9
+24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$
+10bo5bo7bo$11bo3bo$12b2o!",
+    )
+    .expect("built in patterns are valid RLE")
+}
+
+/// Returns the pattern whose function is called `name`, e.g. `"gosper_glider_gun"`.
+pub fn by_name(name: &str) -> Option<Grid<CellState>> {
+    let pattern = match name {
+        "glider" => glider,
+        "blinker" => blinker,
+        "block" => block,
+        "toad" => toad,
+        "beacon" => beacon,
+        "pulsar" => pulsar,
+        "gosper_glider_gun" => gosper_glider_gun,
+        _ => return None,
+    };
+    Some(pattern())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    #[test]
+    fn test_pattern_sizes() {
+        let expected = [
+            (glider(), 3, 3, 5),
+            (blinker(), 3, 1, 3),
+            (block(), 2, 2, 4),
+            (toad(), 4, 2, 6),
+            (beacon(), 4, 4, 8),
+            (pulsar(), 13, 13, 48),
+            (gosper_glider_gun(), 36, 9, 36),
+        ];
+        for (i, (pattern, width, height, population)) in expected.into_iter().enumerate() {
+            assert_eq!(
+                (pattern.width, pattern.height, pattern.population()),
+                (width, height, population),
+                "pattern {i}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_by_name() {
+        let expected = [
+            ("glider", glider()),
+            ("blinker", blinker()),
+            ("block", block()),
+            ("toad", toad()),
+            ("beacon", beacon()),
+            ("pulsar", pulsar()),
+            ("gosper_glider_gun", gosper_glider_gun()),
+        ];
+        for (name, pattern) in expected {
+            assert_eq!(
+                by_name(name).map(|found| found.cells),
+                Some(pattern.cells),
+                "{name}"
+            );
+        }
+        assert!(by_name("Glider").is_none());
+        assert!(by_name("").is_none());
+    }
+
+    #[test]
+    fn test_oscillator_periods() {
+        for (pattern, period) in [(blinker(), 2), (toad(), 2), (beacon(), 2), (pulsar(), 3)] {
+            // Leave room around the pattern for it to oscillate into
+            let mut grid = Grid::new_empty(pattern.width + 4, pattern.height + 4);
+            grid.insert(&pattern, Point::new(2, 2));
+            assert_eq!(grid.detect_period(10), Some(period));
+        }
+    }
+}
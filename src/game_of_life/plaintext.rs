@@ -0,0 +1,146 @@
+//! Reading patterns in the plaintext (`.cells`) format.
+//!
+//! See: <https://conwaylife.com/wiki/Plaintext>
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use super::{CellState, Grid, Point};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `PlaintextError` describes why a pattern could not be read from plaintext
+pub enum PlaintextError {
+    /// A row contains a character the format does not use, such as anything other than `O`,
+    /// `*`, `.` or a space in a Life pattern
+    InvalidCharacter {
+        /// The row of the pattern the character is on, starting from `0`
+        row: usize,
+        /// The unexpected character
+        character: char,
+    },
+    /// The pattern holds more than `Grid::MAX_CELLS` cells
+    TooLarge {
+        /// The width of the widest row
+        width: usize,
+        /// The number of rows
+        height: usize,
+    },
+}
+
+impl Display for PlaintextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaintextError::InvalidCharacter { row, character } => {
+                write!(f, "invalid character '{character}' in row {row}")
+            }
+            PlaintextError::TooLarge { width, height } => write!(
+                f,
+                "pattern of {width} by {height} cells is larger than the {} cells a grid holds",
+                Grid::MAX_CELLS
+            ),
+        }
+    }
+}
+
+impl Error for PlaintextError {}
+
+impl Grid<CellState> {
+    /// Create a new `Grid` from a pattern in plaintext format, sized to its widest row and
+    /// number of rows. `O` and `*` are alive cells, `.` and spaces are dead cells and lines
+    /// starting with `!` are comments. Patterns of more than `Grid::MAX_CELLS` cells are
+    /// rejected.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let glider = Grid::from_plaintext("!Name: Glider\n.O\n..O\nOOO").unwrap();
+    /// assert_eq!(glider.population(), 5);
+    /// ```
+    pub fn from_plaintext(plaintext: &str) -> Result<Self, PlaintextError> {
+        let mut rows: Vec<&str> = plaintext
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.starts_with('!'))
+            .collect();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
+        }
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        if !Grid::within_cap(width, rows.len()) {
+            return Err(PlaintextError::TooLarge {
+                width,
+                height: rows.len(),
+            });
+        }
+
+        let mut grid = Grid::new_empty(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                match character {
+                    'O' | '*' => {
+                        grid.set_cell(
+                            Point::new(x as i32, y as i32),
+                            CellState::Alive(grid.alive_glyph),
+                        );
+                    }
+                    '.' | ' ' => {}
+                    character => {
+                        return Err(PlaintextError::InvalidCharacter { row: y, character })
+                    }
+                }
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_plaintext_blinker() {
+        let g = Grid::from_plaintext("!Name: Blinker\n!\n.O.\n.O.\n.O.\n").unwrap();
+        assert_eq!(g.width, 3);
+        assert_eq!(g.height, 3);
+        assert_eq!(g.population(), 3);
+        assert_eq!(g.to_string(), " X \n X \n X \n");
+    }
+
+    #[test]
+    fn test_from_plaintext_glider() {
+        // Rows may be ragged and use `*` for alive cells
+        let g = Grid::from_plaintext("!Name: Glider\r\n.*\r\n..O\r\nOOO\r\n\r\n").unwrap();
+        assert_eq!(g.width, 3);
+        assert_eq!(g.height, 3);
+        assert_eq!(g.to_string(), " X \n  X\nXXX\n");
+    }
+
+    #[test]
+    fn test_from_plaintext_too_large() {
+        // A long row and many empty rows make a grid far larger than the text
+        let plaintext = format!("{}\n{}O", "O".repeat(2000), "\n".repeat(1000));
+        assert_eq!(
+            Grid::from_plaintext(&plaintext).unwrap_err(),
+            PlaintextError::TooLarge {
+                width: 2000,
+                height: 1002
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_plaintext_invalid_character() {
+        assert_eq!(
+            Grid::from_plaintext(".O.\n.X.").unwrap_err(),
+            PlaintextError::InvalidCharacter {
+                row: 1,
+                character: 'X'
+            }
+        );
+    }
+}
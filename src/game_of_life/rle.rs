@@ -0,0 +1,360 @@
+//! Reading and writing patterns in the Run Length Encoded (RLE) format.
+//!
+//! See: <https://conwaylife.com/wiki/Run_Length_Encoded>
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use super::{CellState, Grid, Point, Ruleset, RulesetError};
+
+/// Lines of the pattern should not be longer than this
+const MAX_LINE_LENGTH: usize = 70;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+/// `RleError` describes why a pattern could not be read from RLE
+pub enum RleError {
+    /// There is no `x = .., y = ..` header line
+    MissingHeader,
+    /// The header line could not be parsed
+    InvalidHeader(String),
+    /// The `rule` in the header is not valid B/S notation
+    InvalidRule(RulesetError),
+    /// A run count is zero, too large or not followed by a tag
+    InvalidCount,
+    /// A character other than `b`, `o`, `$` or `!` was found in the pattern
+    InvalidTag(char),
+    /// The pattern does not fit in the dimensions given in the header
+    OutOfBounds,
+    /// The header dimensions hold more than `Grid::MAX_CELLS` cells
+    TooLarge {
+        /// The `x` of the header
+        width: usize,
+        /// The `y` of the header
+        height: usize,
+    },
+}
+
+impl Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleError::MissingHeader => write!(f, "expected a header line like 'x = 3, y = 3'"),
+            RleError::InvalidHeader(header) => write!(f, "invalid header '{header}'"),
+            RleError::InvalidRule(err) => write!(f, "invalid rule: {err}"),
+            RleError::InvalidCount => write!(f, "invalid run count"),
+            RleError::InvalidTag(c) => write!(f, "invalid tag '{c}'"),
+            RleError::OutOfBounds => write!(f, "pattern is larger than its header dimensions"),
+            RleError::TooLarge { width, height } => write!(
+                f,
+                "pattern of {width} by {height} cells is larger than the {} cells a grid holds",
+                Grid::MAX_CELLS
+            ),
+        }
+    }
+}
+
+impl Error for RleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RleError::InvalidRule(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Grid<CellState> {
+    /// Create a new `Grid` from a pattern in RLE format, sized to the `x` and `y` of its header.
+    /// A `rule` in the header is used as the grid's `Ruleset`.
+    /// Lines starting with `#` are comments and anything after the closing `!` is ignored.
+    /// Headers of more than `Grid::MAX_CELLS` cells are rejected, as patterns are often
+    /// untrusted input.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let glider = Grid::from_rle("#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").unwrap();
+    /// assert_eq!(glider.population(), 5);
+    /// ```
+    pub fn from_rle(rle: &str) -> Result<Self, RleError> {
+        let mut lines = rle
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let header = lines.next().ok_or(RleError::MissingHeader)?;
+        let (width, height, rule) = parse_header(header)?;
+        if !Grid::within_cap(width, height) {
+            return Err(RleError::TooLarge { width, height });
+        }
+
+        let mut grid = Grid::new_empty(width, height);
+        if let Some(rule) = rule {
+            grid.set_ruleset(rule);
+        }
+        let (mut x, mut y): (usize, usize) = (0, 0);
+        let mut count: Option<usize> = None;
+        'pattern: for line in lines {
+            for c in line.chars() {
+                match c {
+                    '0'..='9' => {
+                        let digit = c.to_digit(10).unwrap_or_default() as usize;
+                        count = count
+                            .unwrap_or_default()
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(digit));
+                        if count.is_none() {
+                            return Err(RleError::InvalidCount);
+                        }
+                    }
+                    'b' | 'o' | '$' => {
+                        let run = match count.take() {
+                            Some(0) => return Err(RleError::InvalidCount),
+                            Some(run) => run,
+                            None => 1,
+                        };
+                        // Runs reaching past the end of `usize` are out of bounds of any grid
+                        match c {
+                            'o' => {
+                                let end = x.checked_add(run).ok_or(RleError::OutOfBounds)?;
+                                for x in x..end {
+                                    let p = to_point(x, y).ok_or(RleError::OutOfBounds)?;
+                                    if !grid.set_cell(p, CellState::Alive(grid.alive_glyph)) {
+                                        return Err(RleError::OutOfBounds);
+                                    }
+                                }
+                                x = end;
+                            }
+                            'b' => x = x.checked_add(run).ok_or(RleError::OutOfBounds)?,
+                            _ => {
+                                x = 0;
+                                y = y.checked_add(run).ok_or(RleError::OutOfBounds)?;
+                            }
+                        }
+                    }
+                    '!' if count.is_none() => break 'pattern,
+                    '!' => return Err(RleError::InvalidCount),
+                    c if c.is_whitespace() => {}
+                    c => return Err(RleError::InvalidTag(c)),
+                }
+            }
+        }
+        if count.is_some() {
+            return Err(RleError::InvalidCount);
+        }
+        Ok(grid)
+    }
+}
+
+impl Grid<CellState> {
+    /// Encode the grid as a pattern in RLE format, with the grid dimensions in the header and
+    /// its `Ruleset` if one is set. Dead cells at the end of rows and empty rows at the end of
+    /// the grid are omitted, the header dimensions already cover them. `Dying` cells are dead.
+    /// ```
+    /// use game_of_life::Grid;
+    /// let glider = Grid::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+    /// assert_eq!(glider.to_rle(), "x = 3, y = 3\nbo$2bo$3o!\n");
+    /// ```
+    pub fn to_rle(&self) -> String {
+        let mut rle = format!("x = {}, y = {}", self.width, self.height);
+        if let Some(rule) = self.ruleset() {
+            rle.push_str(&format!(", rule = {rule}"));
+        }
+        rle.push('\n');
+
+        let mut tokens = Vec::new();
+        let mut rows_ended = 0;
+        for row in self.cells.chunks(self.width.max(1)) {
+            if let Some(last) = row.iter().rposition(CellState::is_alive) {
+                if rows_ended > 0 {
+                    tokens.push(run(rows_ended, '$'));
+                }
+                for cells in row[..=last].chunk_by(|a, b| a.is_alive() == b.is_alive()) {
+                    let tag = if cells[0].is_alive() { 'o' } else { 'b' };
+                    tokens.push(run(cells.len(), tag));
+                }
+                rows_ended = 0;
+            }
+            rows_ended += 1;
+        }
+        tokens.push("!".to_string());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length + token.len() > MAX_LINE_LENGTH {
+                rle.push('\n');
+                line_length = 0;
+            }
+            line_length += token.len();
+            rle.push_str(&token);
+        }
+        rle.push('\n');
+        rle
+    }
+}
+
+/// Encodes a run of `count` tags, leaving out a count of 1
+fn run(count: usize, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{count}{tag}")
+    }
+}
+
+fn to_point(x: usize, y: usize) -> Option<Point> {
+    Some(Point::new(x.try_into().ok()?, y.try_into().ok()?))
+}
+
+/// Parses a header like `x = 3, y = 3, rule = B3/S23` into the pattern dimensions and rule
+fn parse_header(header: &str) -> Result<(usize, usize, Option<Ruleset>), RleError> {
+    let invalid = || RleError::InvalidHeader(header.to_string());
+    let (mut width, mut height, mut rule) = (None, None, None);
+    for field in header.split(',') {
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = Some(value.parse().map_err(|_| invalid())?),
+            "y" => height = Some(value.parse().map_err(|_| invalid())?),
+            "rule" => rule = Some(Ruleset::parse(value).map_err(RleError::InvalidRule)?),
+            _ => return Err(invalid()),
+        }
+    }
+    Ok((
+        width.ok_or_else(invalid)?,
+        height.ok_or_else(invalid)?,
+        rule,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = "#N Glider
+#C A comment about the glider
+x = 3, y = 3, rule = B3/S23
+bob$2bo$3o!
+";
+
+    #[test]
+    fn test_from_rle_glider() {
+        let g = Grid::from_rle(GLIDER).unwrap();
+        assert_eq!(g.width, 3);
+        assert_eq!(g.height, 3);
+        assert_eq!(g.population(), 5);
+        assert_eq!(g.ruleset(), Some(Ruleset::conway()));
+        assert_eq!(g.to_string(), " X \n  X\nXXX\n");
+    }
+
+    #[test]
+    fn test_from_rle_multiline_and_blank_rows() {
+        let g = Grid::from_rle("x = 4, y = 4\n  o2b\n o$\n2$3bo !  \n").unwrap();
+        assert_eq!(g.to_string(), "X  X\n    \n    \n   X\n");
+    }
+
+    #[test]
+    fn test_from_rle_without_rule() {
+        let g = Grid::from_rle("x = 2, y = 1\n2o!").unwrap();
+        assert_eq!(g.ruleset(), None);
+        assert_eq!(g.population(), 2);
+    }
+
+    #[test]
+    fn test_from_rle_errors() {
+        assert_eq!(
+            Grid::from_rle("# only a comment").unwrap_err(),
+            RleError::MissingHeader
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3\nooo!").unwrap_err(),
+            RleError::InvalidHeader("x = 3".to_string())
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1, rule = 23/3\nooo!").unwrap_err(),
+            RleError::InvalidRule(RulesetError::MissingPrefix('B'))
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\n0o!").unwrap_err(),
+            RleError::InvalidCount
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\n3!").unwrap_err(),
+            RleError::InvalidCount
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\n2").unwrap_err(),
+            RleError::InvalidCount
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\n99999999999999999999o!").unwrap_err(),
+            RleError::InvalidCount
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\nozo!").unwrap_err(),
+            RleError::InvalidTag('z')
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\n4o!").unwrap_err(),
+            RleError::OutOfBounds
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\no$o!").unwrap_err(),
+            RleError::OutOfBounds
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\no18446744073709551615bo!").unwrap_err(),
+            RleError::OutOfBounds
+        );
+        assert_eq!(
+            Grid::from_rle("x = 3, y = 1\n$18446744073709551615$o!").unwrap_err(),
+            RleError::OutOfBounds
+        );
+        assert_eq!(
+            Grid::from_rle("x = 100000, y = 100000\no!").unwrap_err(),
+            RleError::TooLarge {
+                width: 100_000,
+                height: 100_000
+            }
+        );
+        assert!(matches!(
+            Grid::from_rle("x = 18446744073709551615, y = 2\no!"),
+            Err(RleError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_to_rle_glider() {
+        let g = Grid::from_rle(GLIDER).unwrap();
+        assert_eq!(g.to_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    }
+
+    #[test]
+    fn test_to_rle_skips_empty_rows() {
+        let mut g = Grid::new_empty(5, 6);
+        g.set_cell(Point::new(1, 1), CellState::Alive(g.alive_glyph));
+        g.set_cell(Point::new(4, 4), CellState::Alive(g.alive_glyph));
+        assert_eq!(g.to_rle(), "x = 5, y = 6\n$bo3$4bo!\n");
+        assert_eq!(Grid::new_empty(2, 2).to_rle(), "x = 2, y = 2\n!\n");
+    }
+
+    #[test]
+    fn test_to_rle_wraps_long_lines() {
+        let mut g = Grid::new_empty(200, 1);
+        for x in (0..200).step_by(2) {
+            g.set_cell(Point::new(x, 0), CellState::Alive(g.alive_glyph));
+        }
+        let rle = g.to_rle();
+        assert!(rle.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert_eq!(Grid::from_rle(&rle).unwrap().cells, g.cells);
+    }
+
+    #[test]
+    fn test_rle_round_trip() {
+        for _ in 0..10 {
+            let g = Grid::new_random(17, 11);
+            let round_trip = Grid::from_rle(&g.to_rle()).unwrap();
+            assert_eq!(round_trip.width, g.width);
+            assert_eq!(round_trip.height, g.height);
+            assert_eq!(round_trip.cells, g.cells);
+            assert_eq!(round_trip.population(), g.population());
+        }
+    }
+}
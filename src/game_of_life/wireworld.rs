@@ -0,0 +1,164 @@
+//! Wireworld, an automaton of electrons flowing along wires.
+//!
+//! See: <https://en.wikipedia.org/wiki/Wireworld>
+
+use super::{plaintext::PlaintextError, Cell, Grid, Point};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+/// `Wireworld` is the state of a cell in Wireworld
+/// - An electron head becomes an electron tail
+/// - An electron tail becomes a conductor
+/// - A conductor becomes an electron head if exactly 1 or 2 of its neighbours are electron heads
+/// - Empty cells stay empty
+pub enum Wireworld {
+    /// Nothing, electrons never flow here
+    #[default]
+    Empty,
+    /// The front of an electron
+    ElectronHead,
+    /// The back of an electron, stopping it from flowing backwards
+    ElectronTail,
+    /// A piece of wire
+    Conductor,
+}
+
+impl Wireworld {
+    /// The conventional color of the cell, `None` for empty cells
+    pub fn color(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Wireworld::Empty => None,
+            Wireworld::ElectronHead => Some((80, 140, 255)),
+            Wireworld::ElectronTail => Some((240, 70, 60)),
+            Wireworld::Conductor => Some((230, 180, 40)),
+        }
+    }
+}
+
+impl Cell for Wireworld {
+    fn step(self, neighbours: &[Self]) -> Self {
+        match self {
+            Wireworld::Empty => Wireworld::Empty,
+            Wireworld::ElectronHead => Wireworld::ElectronTail,
+            Wireworld::ElectronTail => Wireworld::Conductor,
+            Wireworld::Conductor => {
+                let heads = neighbours
+                    .iter()
+                    .filter(|&&n| n == Wireworld::ElectronHead)
+                    .count();
+                if (1..=2).contains(&heads) {
+                    Wireworld::ElectronHead
+                } else {
+                    Wireworld::Conductor
+                }
+            }
+        }
+    }
+}
+
+impl Grid<Wireworld> {
+    /// Create a new Wireworld `Grid` of a given `width` and `height` with every cell empty
+    pub fn new_wireworld(width: usize, height: usize) -> Self {
+        Grid::new_filled(width, height, Wireworld::Empty)
+    }
+
+    /// Create a new Wireworld `Grid` from a circuit in plaintext, sized like
+    /// `Grid::from_plaintext`. `#` is a conductor, `H` an electron head, `t` an electron tail,
+    /// `.` and spaces are empty and lines starting with `!` are comments. Circuits of more than
+    /// `Grid::MAX_CELLS` cells are rejected.
+    /// ```
+    /// use game_of_life::{wireworld::Wireworld, Grid, Point};
+    /// let mut wire = Grid::from_wireworld_plaintext("tH##").unwrap();
+    /// wire.evolve();
+    /// assert_eq!(wire[Point::new(2, 0)], Wireworld::ElectronHead);
+    /// ```
+    pub fn from_wireworld_plaintext(plaintext: &str) -> Result<Self, PlaintextError> {
+        let mut rows: Vec<&str> = plaintext
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.starts_with('!'))
+            .collect();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
+        }
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        if !Grid::within_cap(width, rows.len()) {
+            return Err(PlaintextError::TooLarge {
+                width,
+                height: rows.len(),
+            });
+        }
+
+        let mut grid = Grid::new_wireworld(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                grid[Point::new(x as i32, y as i32)] = match character {
+                    '#' => Wireworld::Conductor,
+                    'H' => Wireworld::ElectronHead,
+                    't' => Wireworld::ElectronTail,
+                    '.' | ' ' => Wireworld::Empty,
+                    character => {
+                        return Err(PlaintextError::InvalidCharacter { row: y, character })
+                    }
+                };
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_electron_travels_along_wire() {
+        let mut wire = Grid::from_wireworld_plaintext("tH######").unwrap();
+        for generation in 1..=6 {
+            wire.evolve();
+            let row: String = wire
+                .cells
+                .iter()
+                .map(|cell| match cell {
+                    Wireworld::ElectronHead => 'H',
+                    Wireworld::ElectronTail => 't',
+                    _ => '#',
+                })
+                .collect();
+            let mut expected = "########".to_string();
+            expected.replace_range(generation..generation + 2, "tH");
+            assert_eq!(row, expected, "generation {generation}");
+        }
+    }
+
+    #[test]
+    fn test_from_wireworld_plaintext() {
+        let grid = Grid::from_wireworld_plaintext("!Diode\n.##\n#Ht\n").unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid[Point::new(0, 0)], Wireworld::Empty);
+        assert_eq!(grid[Point::new(1, 1)], Wireworld::ElectronHead);
+        assert_eq!(grid[Point::new(2, 1)], Wireworld::ElectronTail);
+        assert_eq!(
+            Grid::from_wireworld_plaintext("#O#").unwrap_err(),
+            PlaintextError::InvalidCharacter {
+                row: 0,
+                character: 'O'
+            }
+        );
+    }
+
+    #[test]
+    fn test_three_heads_block_conductor() {
+        assert_eq!(
+            Wireworld::Conductor.step(&[Wireworld::ElectronHead; 3]),
+            Wireworld::Conductor
+        );
+        assert_eq!(
+            Wireworld::Conductor.step(&[Wireworld::ElectronHead; 2]),
+            Wireworld::ElectronHead
+        );
+    }
+}
@@ -1,10 +1,18 @@
+#[cfg(feature = "native")]
+mod native;
+mod theme;
+mod web;
+
+use std::cell::{Cell, RefCell};
 use std::io;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
 
-use gridlife::{CellState, Grid};
-use ratzilla::ratatui::layout::{Constraint, Flex, Layout, Offset, Rect};
+use ratzilla::event::{KeyCode, KeyEvent};
+use ratzilla::ratatui::layout::{Constraint, Flex, Layout, Margin, Position, Rect, Size};
 use ratzilla::ratatui::style::{Style, Stylize};
-use ratzilla::ratatui::text::{Line, Span, Text};
-use ratzilla::ratatui::widgets::{BorderType, Clear, Wrap};
+use ratzilla::ratatui::text::{Line, Span};
+use ratzilla::ratatui::widgets::{BorderType, Clear, Padding, Wrap};
 use ratzilla::ratatui::Frame;
 use ratzilla::ratatui::{
     layout::Alignment,
@@ -12,10 +20,16 @@ use ratzilla::ratatui::{
     widgets::{Block, Paragraph},
     Terminal,
 };
-use ratzilla::utils::is_mobile;
+use ratzilla::utils::{is_mobile, open_url};
 use ratzilla::widgets::Hyperlink;
 use ratzilla::{DomBackend, WebRenderer};
 
+use crate::theme::{Theme, THEMES};
+use crate::web::CellMouse;
+use game_of_life::builder::GridBuilder;
+use game_of_life::langton::{Heading, LangtonAnt};
+use game_of_life::{patterns, CellGlyphs, CellState, Grid, GridStatus, Point, Ruleset, Symmetry};
+
 const BANNER: &str = r#"
   _______                  _             _    _____      _ _           _   _           
  |__   __|                (_)           | |  / ____|    | | |         | | (_)          
@@ -25,6 +39,26 @@ const BANNER: &str = r#"
     |_|\___|_|  |_| |_| |_|_|_| |_|\__,_|_|  \_____\___/|_|_|\___|\___|\__|_| \_/ \___|
 "#;
 
+/// The title shown instead of `BANNER` when the screen is too narrow for it.
+const COMPACT_BANNER: &str = "\n>_ Terminal Collective\n";
+
+/// The id of the loading message in `index.html`, shown while the WASM loads and removed once
+/// the terminal is up.
+const SPLASH_ID: &str = "splash";
+
+/// Shown in place of the site when the terminal can't be set up.
+const TERMINAL_FAILED_MESSAGE: &str = "Terminal failed to initialize";
+
+/// Below this many columns the content panels take up most of the screen width.
+const NARROW_WIDTH: u16 = 100;
+
+/// Columns of the content panels not available to the description text, from the borders and
+/// padding around it.
+const DESCRIPTION_MARGIN: u16 = 15;
+
+/// The narrowest the description text is wrapped to, however small the screen.
+const MIN_DESCRIPTION_WIDTH: usize = 10;
+
 const DESCRIPTION: &str = r#"
 >_ Terminal Collective is a community for open-source terminal software enthusiasts.
 
@@ -33,166 +67,3504 @@ We bring together developers of terminal software and users who share a passion
 Our goal is to create a space where people can share their work, learn from each other, and collaborate on terminal-related projects.
 "#;
 
-const LINKS: &[(&str, &str)] = &[
-    ("GitHub", "https://github.com/terminalcollective"),
-    ("Discord", "https://discord.gg/6EUERBrAMs"),
-    ("Twitter", "https://www.youtube.com/@TerminalCollectiveOrg"),
-    ("RSS", "https://terminalcollective.org/feed.xml"),
+/// A link to one of the places the community can be found.
+struct SocialLink {
+    label: &'static str,
+    url: &'static str,
+    /// A single width glyph shown before the label.
+    icon: char,
+}
+
+const LINKS: &[SocialLink] = &[
+    SocialLink {
+        label: "GitHub",
+        url: "https://github.com/terminalcollective",
+        icon: '$',
+    },
+    SocialLink {
+        label: "Discord",
+        url: "https://discord.gg/6EUERBrAMs",
+        icon: '#',
+    },
+    SocialLink {
+        label: "YouTube",
+        url: "https://www.youtube.com/@TerminalCollectiveOrg",
+        icon: '▶',
+    },
+    SocialLink {
+        label: "RSS",
+        url: "https://terminalcollective.org/feed.xml",
+        icon: '~',
+    },
 ];
 
-fn main() -> io::Result<()> {
-    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-    let backend = DomBackend::new()?;
-    let terminal = Terminal::new(backend)?;
-    let size = terminal.size()?;
-    let mut grid = Grid::new_random(size.width.into(), size.height.into());
+/// An upcoming community meetup.
+struct Meetup {
+    title: &'static str,
+    /// The day of the meetup as `YYYY-MM-DD`, which sorts and compares like the date itself.
+    date: &'static str,
+    location: &'static str,
+    url: &'static str,
+}
 
-    terminal.draw_web(move |frame| {
-        render_game_of_life(&mut grid, frame);
+/// Meetups listed in the Meetups panel, past ones are hidden.
+const MEETUPS: &[Meetup] = &[];
+
+/// The most lines the Meetups panel grows to before the list is truncated.
+const MAX_MEETUP_LINES: usize = 3;
 
-        let (vert_perc, hori_perc) = if is_mobile() { (30, 80) } else { (80, 60) };
+/// The characters the background grid is drawn with until another alive glyph is picked.
+const CELL_GLYPHS: CellGlyphs = CellGlyphs {
+    alive: 'X',
+    dead: ' ',
+};
 
-        let vertical = Layout::vertical([Constraint::Percentage(vert_perc)]).flex(Flex::Center);
-        let horizontal = Layout::horizontal([Constraint::Percentage(hori_perc)]).flex(Flex::Center);
-        let [area] = vertical.areas(frame.area());
-        let [area] = horizontal.areas(area);
+/// The glyphs alive cells can be drawn with, cycled through with `a`. Every one is a single
+/// column wide, so the cells stay lined up whichever is picked.
+const ALIVE_GLYPHS: [char; 6] = [CELL_GLYPHS.alive, '0', '█', '●', '#', '*'];
 
-        if is_mobile() {
-            render_mobile(area, frame);
-        } else {
-            render_desktop(area, frame);
+/// The localStorage key the alive glyph picked with `a` is saved under.
+const GLYPH_STORAGE_KEY: &str = "tc_gol_glyph";
+
+/// The colors of the heatmap, from cells barely warm to cells alive all along.
+const HEAT_GRADIENT: [(u8, u8, u8); 3] = [(70, 10, 10), (200, 80, 20), (250, 210, 90)];
+
+/// The colors clusters of touching cells are told apart by, reused once they run out.
+const CLUSTER_COLORS: [(u8, u8, u8); 6] = [
+    (230, 110, 110),
+    (110, 200, 120),
+    (110, 150, 240),
+    (230, 190, 90),
+    (190, 120, 230),
+    (90, 200, 210),
+];
+
+/// The color spaceships are highlighted in.
+const SPACESHIP_COLOR: Color = Color::Rgb(255, 120, 220);
+
+/// The longest period a cluster is evolved for to tell whether it is a spaceship, enough for
+/// gliders and the small spaceships.
+const SPACESHIP_MAX_PERIOD: usize = 4;
+
+/// How far cells born in odd generations are shaded toward the fade color of the theme, for a
+/// subtle two-tone shimmer.
+const SHIMMER_SHADE: f32 = 0.25;
+
+/// The share of the full heat below which a cell is not shown on the heatmap.
+const MIN_VISIBLE_HEAT: f32 = 0.02;
+
+/// The age in generations at which cells reach the end of the age gradient.
+const AGE_GRADIENT_SPAN: u16 = 32;
+
+/// The localStorage key the board is saved under between visits.
+const BOARD_STORAGE_KEY: &str = "tc_gol_state";
+
+/// Prefix of the saved board, bumped whenever its format changes so older saves are ignored.
+const BOARD_FORMAT: &str = "v1\n";
+
+/// Generations computed per second by default, which keeps the background calm enough not to
+/// distract from the content.
+const DEFAULT_GENERATIONS_PER_SECOND: u32 = 15;
+
+/// The range the speed can be adjusted in with `+` and `-`.
+const GENERATIONS_PER_SECOND: RangeInclusive<u32> = 2..=60;
+
+/// Generations computed per second in reduced motion.
+const REDUCED_MOTION_GENERATIONS_PER_SECOND: u32 = 1;
+
+/// The most time a single frame catches up on, in milliseconds. Frames are not rendered while
+/// the tab is in the background, and catching up on all of that time at once would freeze the
+/// page when it comes back.
+const MAX_FRAME_MS: f64 = 250.0;
+
+/// The most generations computed in a single frame, so slow devices skip generations rather
+/// than spend ever longer catching up.
+const MAX_GENERATIONS_PER_FRAME: u32 = 4;
+
+/// The share of cells alive after the board is reseeded.
+const RESEED_DENSITY: f32 = 0.5;
+
+/// Where `g` drops a glider gun, in the top left corner so its gliders cross the board.
+const GLIDER_GUN_POSITION: Point = Point::new(2, 2);
+
+/// Generations kept to step back through with the left arrow.
+const HISTORY_LENGTH: usize = 64;
+
+/// Generations the board may stay settled into still lifes and blinkers before it is reseeded.
+const RESEED_AFTER_SETTLED: u32 = 30;
+
+/// The size in pixels of every cell in exported images.
+const EXPORT_CELL_PIXELS: u32 = 4;
+
+/// The name exported images are downloaded as.
+const EXPORT_FILENAME: &str = "game-of-life.png";
+
+/// How many frames a toast stays in the status line.
+const TOAST_FRAMES: u32 = 90;
+
+/// How many frames the old board fades out and the new one fades in over after a reseed.
+const CROSS_FADE_FRAMES: u32 = 30;
+
+/// The chance of every dead cell coming to life each generation while it rains.
+const RAIN_PROBABILITY: f32 = 0.0005;
+
+/// Generations between spawns of the glider rain, unless `?rain_interval=` is given.
+const GLIDER_RAIN_INTERVAL: u32 = 20;
+
+/// The chance of a glider spawning in each slot along the top edge, unless `?rain_density=` is
+/// given.
+const GLIDER_RAIN_DENSITY: f32 = 0.15;
+
+/// Columns of every slot along the top edge a glider of the glider rain may spawn in, the
+/// glider with a column of room on both sides.
+const GLIDER_RAIN_SLOT: usize = 5;
+
+/// Seeds the glider rain, so it falls the same way on every visit unless `?seed=` is given.
+const GLIDER_RAIN_SEED: u64 = 1970;
+
+/// Cells the view moves by with `Shift` and the arrow keys, on grids larger than the screen.
+const PAN_STEP: i32 = 8;
+
+/// The most columns and rows of blocks the minimap shows inside its border.
+const MINIMAP_SIZE: (usize, usize) = (24, 8);
+
+/// Something a key does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    TogglePause,
+    Step,
+    StepBack,
+    SpeedUp,
+    SlowDown,
+    Clear,
+    Reseed,
+    GliderGun,
+    ToggleMotion,
+    ToggleRain,
+    ToggleCellWidth,
+    CycleGlyph,
+    ExportPng,
+    CopyRle,
+    ToggleHud,
+    CycleRuleset,
+    ToggleMask,
+    ToggleInspect,
+    ToggleHeat,
+    ToggleShimmer,
+    ToggleClusters,
+    ToggleSpaceships,
+    NextTheme,
+    ToggleLight,
+    NextLink,
+    PreviousLink,
+    Unfocus,
+    OpenLink,
+    ToggleHelp,
+}
+
+impl Action {
+    /// What the action does, as listed in the help overlay.
+    fn description(self) -> &'static str {
+        match self {
+            Action::PanLeft => "Pan the view left",
+            Action::PanRight => "Pan the view right",
+            Action::PanUp => "Pan the view up",
+            Action::PanDown => "Pan the view down",
+            Action::TogglePause => "Pause or resume",
+            Action::Step => "Step one generation",
+            Action::StepBack => "Step back a generation",
+            Action::SpeedUp => "Speed up",
+            Action::SlowDown => "Slow down",
+            Action::Clear => "Clear the board",
+            Action::Reseed => "Reseed the board",
+            Action::GliderGun => "Place a glider gun",
+            Action::ToggleMotion => "Toggle reduced motion",
+            Action::ToggleRain => "Toggle random rain",
+            Action::ToggleCellWidth => "Toggle double width cells",
+            Action::CycleGlyph => "Next cell glyph",
+            Action::ExportPng => "Save the board as a PNG",
+            Action::CopyRle => "Copy the board as RLE",
+            Action::ToggleHud => "Toggle the performance overlay",
+            Action::CycleRuleset => "Next ruleset",
+            Action::ToggleMask => "Toggle hiding cells under the panels",
+            Action::ToggleInspect => "Toggle the cell tooltip",
+            Action::ToggleHeat => "Toggle the heatmap",
+            Action::ToggleShimmer => "Toggle shading cells by when they were born",
+            Action::ToggleClusters => "Toggle coloring clusters of cells",
+            Action::ToggleSpaceships => "Toggle highlighting spaceships",
+            Action::NextTheme => "Next theme",
+            Action::ToggleLight => "Toggle light and dark themes",
+            Action::NextLink => "Focus the next link",
+            Action::PreviousLink => "Focus the previous link",
+            Action::Unfocus => "Unfocus the link, or close this help",
+            Action::OpenLink => "Open the focused link",
+            Action::ToggleHelp => "Toggle this help",
         }
-    });
+    }
+}
 
-    Ok(())
+/// A key bound to an `Action`. Keys bound without `shift` work with or without Shift held, so
+/// characters typed with Shift, such as `+` and `?`, still reach them.
+struct Keybinding {
+    code: KeyCode,
+    shift: bool,
+    action: Action,
 }
 
-fn render_mobile(area: Rect, frame: &mut Frame) {
-    let constraints = [
-        Constraint::Length(3),
-        Constraint::Length(LINKS.len() as u16 + 2),
-    ];
-    render_background(
-        frame,
-        area,
-        Some("Terminal Collective".to_string()),
-        &constraints,
-    );
-    let [meetups_area, links_area] = Layout::vertical(constraints).areas(area);
-    render_meetups(frame, meetups_area);
-    render_links(frame, links_area);
+impl Keybinding {
+    const fn new(code: KeyCode, action: Action) -> Self {
+        Self {
+            code,
+            shift: false,
+            action,
+        }
+    }
+
+    const fn shifted(code: KeyCode, action: Action) -> Self {
+        Self {
+            code,
+            shift: true,
+            action,
+        }
+    }
+
+    /// Whether `event` triggers this binding.
+    fn matches(&self, event: &KeyEvent) -> bool {
+        self.code == event.code && (event.shift || !self.shift)
+    }
+
+    /// The key as shown in the help overlay, e.g. `Shift+←`.
+    fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(character) => character.to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            ref code => format!("{code:?}"),
+        };
+        if self.shift {
+            format!("Shift+{key}")
+        } else {
+            key
+        }
+    }
 }
 
-fn render_desktop(area: Rect, frame: &mut Frame) {
-    let description = textwrap::wrap(DESCRIPTION.trim(), area.width as usize - 15)
+/// Every key the site responds to, in the order the help overlay lists them. The first binding
+/// matching a key wins, so bindings needing Shift come before the same key without it.
+const KEYBINDINGS: &[Keybinding] = &[
+    Keybinding::new(KeyCode::Char('?'), Action::ToggleHelp),
+    Keybinding::new(KeyCode::Char(' '), Action::TogglePause),
+    Keybinding::shifted(KeyCode::Left, Action::PanLeft),
+    Keybinding::shifted(KeyCode::Right, Action::PanRight),
+    Keybinding::shifted(KeyCode::Up, Action::PanUp),
+    Keybinding::shifted(KeyCode::Down, Action::PanDown),
+    Keybinding::new(KeyCode::Char('n'), Action::Step),
+    Keybinding::new(KeyCode::Right, Action::Step),
+    Keybinding::new(KeyCode::Char('b'), Action::StepBack),
+    Keybinding::new(KeyCode::Left, Action::StepBack),
+    Keybinding::new(KeyCode::Char('+'), Action::SpeedUp),
+    Keybinding::new(KeyCode::Char('='), Action::SpeedUp),
+    Keybinding::new(KeyCode::Char('-'), Action::SlowDown),
+    Keybinding::new(KeyCode::Char('c'), Action::Clear),
+    Keybinding::new(KeyCode::Char('r'), Action::Reseed),
+    Keybinding::new(KeyCode::Char('g'), Action::GliderGun),
+    Keybinding::new(KeyCode::Char('u'), Action::CycleRuleset),
+    Keybinding::new(KeyCode::Char('w'), Action::ToggleRain),
+    Keybinding::new(KeyCode::Char('m'), Action::ToggleMotion),
+    Keybinding::new(KeyCode::Char('d'), Action::ToggleCellWidth),
+    Keybinding::new(KeyCode::Char('a'), Action::CycleGlyph),
+    Keybinding::new(KeyCode::Char('h'), Action::ToggleHeat),
+    Keybinding::new(KeyCode::Char('e'), Action::ToggleShimmer),
+    Keybinding::new(KeyCode::Char('o'), Action::ToggleClusters),
+    Keybinding::new(KeyCode::Char('v'), Action::ToggleSpaceships),
+    Keybinding::new(KeyCode::Char('k'), Action::ToggleMask),
+    Keybinding::new(KeyCode::Char('i'), Action::ToggleInspect),
+    Keybinding::new(KeyCode::Char('f'), Action::ToggleHud),
+    Keybinding::new(KeyCode::Char('t'), Action::NextTheme),
+    Keybinding::new(KeyCode::Char('l'), Action::ToggleLight),
+    Keybinding::new(KeyCode::Char('s'), Action::ExportPng),
+    Keybinding::new(KeyCode::Char('y'), Action::CopyRle),
+    Keybinding::shifted(KeyCode::Tab, Action::PreviousLink),
+    Keybinding::new(KeyCode::Tab, Action::NextLink),
+    Keybinding::new(KeyCode::Down, Action::NextLink),
+    Keybinding::new(KeyCode::Up, Action::PreviousLink),
+    Keybinding::new(KeyCode::Enter, Action::OpenLink),
+    Keybinding::new(KeyCode::Esc, Action::Unfocus),
+];
+
+/// The lines of the help overlay, one per action with all of its keys.
+fn help_lines() -> Vec<String> {
+    let mut actions: Vec<(Action, Vec<String>)> = Vec::new();
+    for binding in KEYBINDINGS {
+        match actions
+            .iter_mut()
+            .find(|(action, _)| *action == binding.action)
+        {
+            Some((_, keys)) => keys.push(binding.label()),
+            None => actions.push((binding.action, vec![binding.label()])),
+        }
+    }
+    let keys: Vec<String> = actions.iter().map(|(_, keys)| keys.join(", ")).collect();
+    let width = keys
         .iter()
-        .map(|line| line.to_string())
-        .collect::<Vec<String>>()
-        .join("\n");
-    let constraints = [
-        Constraint::Length(BANNER.lines().count() as u16 + 1),
-        Constraint::Length(description.lines().count() as u16 + 2),
-        Constraint::Length(3),
-        Constraint::Length(LINKS.len() as u16 + 2),
-    ];
-    render_background(frame, area, None, &constraints);
-    let [banner_area, description_area, meetups_area, links_area] =
-        Layout::vertical(constraints).areas(area);
-    render_banner(frame, banner_area);
-    render_description(frame, description, description_area);
-    render_meetups(frame, meetups_area);
-    render_links(frame, links_area);
+        .map(|keys| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+    actions
+        .iter()
+        .zip(keys)
+        .map(|((action, _), keys)| {
+            let padding = width - keys.chars().count();
+            format!("{keys}{}  {}", " ".repeat(padding), action.description())
+        })
+        .collect()
 }
 
-fn render_game_of_life(grid: &mut Grid<CellState>, frame: &mut Frame<'_>) {
-    grid.update_states();
-    let grid_out = grid.to_string();
-    let lines: Vec<Line> = grid_out.lines().map(Line::from).collect();
-    let grid_text = Text::from(lines).fg(Color::Rgb(100, 100, 100));
-    frame.render_widget(Paragraph::new(grid_text), frame.area());
+/// A ruleset the background can be switched to with `u`.
+struct NamedRuleset {
+    name: &'static str,
+    ruleset: Ruleset,
 }
 
-fn render_links(frame: &mut Frame<'_>, links_area: Rect) {
-    frame.render_widget(Block::bordered().title("Links".bold()), links_area);
-    for (i, (text, url)) in LINKS.iter().enumerate() {
-        let label = Span::raw(*text);
-        let link = Hyperlink::new(*url);
+/// The rulesets cycled through with `u`, starting from the standard Conway rules.
+const RULESETS: &[NamedRuleset] = &[
+    NamedRuleset {
+        name: "Conway",
+        ruleset: Ruleset::conway(),
+    },
+    NamedRuleset {
+        name: "HighLife",
+        ruleset: Ruleset::highlife(),
+    },
+    NamedRuleset {
+        name: "Day & Night",
+        ruleset: Ruleset::day_and_night(),
+    },
+    NamedRuleset {
+        name: "Seeds",
+        ruleset: Ruleset::seeds(),
+    },
+    NamedRuleset {
+        name: "Life without Death",
+        ruleset: Ruleset::life_without_death(),
+    },
+];
 
-        frame.render_widget(
-            label,
-            links_area.offset(Offset {
-                x: 1,
-                y: i as i32 + 1,
-            }),
-        );
+/// Decides whether the simulation advances on a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Simulation {
+    Running,
+    Paused,
+    /// Paused, but advancing a single generation on the next frame.
+    Step,
+}
 
-        frame.render_widget(
-            link,
-            links_area.offset(Offset {
-                x: 10,
-                y: i as i32 + 1,
-            }),
-        );
+impl Simulation {
+    /// Returns whether a generation should be computed for this frame.
+    fn advance(&mut self) -> bool {
+        match self {
+            Simulation::Running => true,
+            Simulation::Paused => false,
+            Simulation::Step => {
+                *self = Simulation::Paused;
+                true
+            }
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        *self = match self {
+            Simulation::Running => Simulation::Paused,
+            Simulation::Paused | Simulation::Step => Simulation::Running,
+        };
+    }
+
+    /// Advances a single generation, only while paused.
+    fn step(&mut self) {
+        if *self == Simulation::Paused {
+            *self = Simulation::Step;
+        }
     }
 }
 
-fn render_meetups(frame: &mut Frame<'_>, meetups_area: Rect) {
-    frame.render_widget(
-        Paragraph::new("Coming soon!").block(Block::bordered().title("Meetups".bold())),
-        meetups_area,
-    );
+/// How much the background is allowed to move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MotionMode {
+    Full,
+    /// Slows the simulation down for visitors who asked for less motion.
+    Reduced,
 }
 
-fn render_description(frame: &mut Frame<'_>, description: String, description_area: Rect) {
-    frame.render_widget(
-        Paragraph::new(description)
-            .wrap(Wrap { trim: true })
-            .left_aligned()
-            .block(Block::bordered()),
-        description_area,
-    );
+impl MotionMode {
+    fn toggle(&mut self) {
+        *self = match self {
+            MotionMode::Full => MotionMode::Reduced,
+            MotionMode::Reduced => MotionMode::Full,
+        };
+    }
 }
 
-fn render_banner(frame: &mut Frame<'_>, banner_area: Rect) {
-    frame.render_widget(
-        Paragraph::new(BANNER).alignment(Alignment::Center),
-        banner_area,
-    );
+/// How many terminal columns every cell of the grid is rendered across.
+///
+/// Terminal cells are about twice as tall as they are wide, so with one column per cell circular
+/// patterns look like ovals and gliders look skewed. Two columns make cells roughly square, but
+/// halve the number of cells that fit across the screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellWidth {
+    Single,
+    Double,
 }
 
-fn render_background(
-    frame: &mut Frame<'_>,
-    area: Rect,
-    title: Option<String>,
-    constraints: &[Constraint],
-) {
-    let mut area = Rect::new(
-        area.x - 2,
-        area.y - 1,
-        area.width + 4,
-        constraints
-            .iter()
-            .map(|c| match *c {
-                Constraint::Min(v) | Constraint::Max(v) | Constraint::Length(v) => v,
-                _ => 0,
-            })
-            .sum::<u16>()
-            + 3,
-    );
-    area = area.clamp(frame.area());
-    let mut block = Block::bordered()
-        .border_type(BorderType::Rounded)
-        .border_style(Color::Rgb(73, 222, 128))
-        .style(
-            Style::default()
-                .fg(Color::Rgb(73, 222, 128))
-                .bg(Color::Rgb(16, 24, 39)),
+impl CellWidth {
+    fn toggle(&mut self) {
+        *self = match self {
+            CellWidth::Single => CellWidth::Double,
+            CellWidth::Double => CellWidth::Single,
+        };
+    }
+
+    fn columns(self) -> u16 {
+        match self {
+            CellWidth::Single => 1,
+            CellWidth::Double => 2,
+        }
+    }
+}
+
+/// The glider rain selected with `?mode=glider_rain`: every `interval` generations gliders spawn
+/// at random columns of the top edge, heading down to the left or right.
+struct GliderRain {
+    interval: u32,
+    /// The chance of a glider spawning in each slot along the top edge.
+    density: f32,
+    rng: fastrand::Rng,
+}
+
+impl GliderRain {
+    fn new(interval: u32, density: f32, seed: u64) -> Self {
+        Self {
+            interval: interval.max(1),
+            density: density.clamp(0.0, 1.0),
+            rng: fastrand::Rng::with_seed(seed),
+        }
+    }
+
+    /// Spawns gliders along the top edge of `grid` if they are due in `generation`, returning
+    /// how many did. Slots with any alive cells around them are skipped, so new gliders don't
+    /// smash into what is already there.
+    fn spawn(&mut self, grid: &mut Grid<CellState>, generation: u64) -> usize {
+        let glider = patterns::glider();
+        if !generation.is_multiple_of(u64::from(self.interval)) || grid.height < glider.height {
+            return 0;
+        }
+        let mut spawned = 0;
+        for slot in 0..grid.width / GLIDER_RAIN_SLOT {
+            let (spawns, leftwards) = (self.rng.f32() < self.density, self.rng.bool());
+            let left = (slot * GLIDER_RAIN_SLOT) as i32;
+            let occupied = (left..left + GLIDER_RAIN_SLOT as i32).any(|x| {
+                (0..=glider.height as i32).any(|y| {
+                    grid.try_get(Point::new(x, y))
+                        .is_some_and(CellState::is_alive)
+                })
+            });
+            if !spawns || occupied {
+                continue;
+            }
+            let mut glider = glider.clone();
+            if leftwards {
+                glider.flip_horizontal();
+            }
+            grid.insert(&glider, Point::new(left + 1, 0));
+            spawned += 1;
+        }
+        spawned
+    }
+}
+
+/// A short message shown in the status line for a while, e.g. after copying the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Toast {
+    text: &'static str,
+    frames_left: u32,
+}
+
+impl Toast {
+    fn new(text: &'static str) -> Self {
+        Self {
+            text,
+            frames_left: TOAST_FRAMES,
+        }
+    }
+}
+
+/// The cells of the board before it was reseeded, fading out while the new cells fade in.
+#[derive(Clone, Debug, PartialEq)]
+struct CrossFade {
+    old: Vec<CellState>,
+    /// The glyph the old alive cells were drawn with.
+    alive: char,
+    frames_left: u32,
+}
+
+impl CrossFade {
+    fn new(old: Vec<CellState>, alive: char) -> Self {
+        Self {
+            old,
+            alive,
+            frames_left: CROSS_FADE_FRAMES,
+        }
+    }
+
+    /// How far along the fade is, from `0.0` when it starts to `1.0` on its last frame.
+    fn progress(&self) -> f32 {
+        1.0 - self.frames_left as f32 / CROSS_FADE_FRAMES as f32
+    }
+}
+
+/// A stroke being painted, or a rectangle being selected, by dragging the mouse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Stroke {
+    /// The cell the mouse button was pressed over.
+    start: Point,
+    /// The cell the pointer was last over.
+    last: Point,
+    /// Whether the stroke kills cells rather than bringing them to life.
+    erase: bool,
+    /// Whether the rectangle between `start` and `last` is filled once the button is released,
+    /// rather than painting the cells the pointer moves over.
+    select: bool,
+}
+
+impl Stroke {
+    /// The state the stroke sets cells of `grid` to.
+    fn state(&self, grid: &Grid<CellState>) -> CellState {
+        if self.erase {
+            CellState::Dead(grid.dead_glyph)
+        } else {
+            CellState::Alive(grid.alive_glyph)
+        }
+    }
+}
+
+/// Performance numbers shown in the overlay toggled with `f`.
+#[derive(Debug, Default)]
+struct Hud {
+    visible: bool,
+    /// When the previous frame was rendered, in milliseconds since the page loaded.
+    last_frame: Option<f64>,
+    /// Frames per second, smoothed over the last frames.
+    fps: f64,
+    /// Milliseconds spent computing the last generation.
+    update_ms: f64,
+}
+
+impl Hud {
+    /// How much a new frame contributes to the smoothed frame rate.
+    const SMOOTHING: f64 = 0.1;
+
+    /// Records that a frame is rendered at `now`, in milliseconds.
+    fn record_frame(&mut self, now: f64) {
+        if let Some(last) = self.last_frame {
+            let delta = now - last;
+            if delta > 0.0 {
+                let fps = 1000.0 / delta;
+                self.fps = if self.fps == 0.0 {
+                    fps
+                } else {
+                    self.fps + (fps - self.fps) * Self::SMOOTHING
+                };
+            }
+        }
+        self.last_frame = Some(now);
+    }
+
+    fn text(&self, population: usize, rule: &str) -> String {
+        format!(
+            "fps {:.1}\nupdate {:.2} ms\ncells {population}\nrule {rule}",
+            self.fps, self.update_ms
         )
-        .title_bottom("|built with Ratzilla|")
-        .title_alignment(Alignment::Right);
-    if let Some(title) = title {
-        block = block.title_top(Line::from(title).alignment(Alignment::Center).bold());
     }
-    frame.render_widget(Clear, area);
-    frame.render_widget(block, area);
+}
+
+/// State shared between the render loop and the browser event handlers.
+struct App {
+    grid: Grid<CellState>,
+    /// The area covered by the content panels, which sit on top of the grid.
+    panels: Rect,
+    /// Whether the grid evolves, it is rendered either way.
+    simulation: Simulation,
+    /// The number of generations computed so far.
+    generation: u64,
+    /// How many generations are computed every second while running, whatever the frame rate.
+    generations_per_second: u32,
+    motion: MotionMode,
+    cell_width: CellWidth,
+    /// The index into `ALIVE_GLYPHS` of the glyph alive cells are drawn with.
+    glyph: usize,
+    /// Whether a few random cells come to life every generation, see `RAIN_PROBABILITY`.
+    rain: bool,
+    /// When the previous frame was rendered, in milliseconds since the page loaded.
+    last_tick: Option<f64>,
+    /// Time accumulated towards the next generation, in milliseconds.
+    pending_ms: f64,
+    /// Generations the grid has been settled for, see `RESEED_AFTER_SETTLED`.
+    settled_generations: u32,
+    /// The index into `LINKS` selected with the keyboard, if any.
+    focused_link: Option<usize>,
+    /// The index into `THEMES` of the active theme.
+    theme: usize,
+    /// Whether the theme was picked by the visitor rather than following their color scheme.
+    theme_chosen: bool,
+    hud: Hud,
+    /// The stroke being painted while the mouse button is down, if any.
+    stroke: Option<Stroke>,
+    /// Whether a stroke was painted, so the click ending it does not toggle a cell.
+    stroke_painted: bool,
+    /// Shared with browser callbacks, which may show a toast once they complete.
+    toast: Rc<Cell<Option<Toast>>>,
+    /// Whether cells under the content panels are left out, so nothing shows around the text.
+    mask_panels: bool,
+    /// The fade from the board before the last reseed to the new one, while it lasts.
+    cross_fade: Option<CrossFade>,
+    /// Whether the cell under the pointer is described in a tooltip.
+    inspect: bool,
+    /// The terminal cell the pointer was last over.
+    hover: Option<Position>,
+    /// Whether the keybindings are listed over everything else.
+    help: bool,
+    /// Whether each cluster of touching cells is drawn in a color of its own.
+    clusters: bool,
+    /// Whether gliders and other spaceships are highlighted as they travel.
+    spaceships: bool,
+    /// The spaceships last found, only searched for again once the board changes.
+    found_spaceships: Vec<Vec<Point>>,
+    /// The generation the spaceships were last searched for at, `None` once the cells or rule
+    /// changed in between generations.
+    spaceships_found_at: Option<u64>,
+    /// The grid cell shown in the top left corner of the screen, moved on grids larger than it.
+    viewport: Point,
+    /// The area covered by the minimap, empty while the whole grid fits on screen.
+    minimap: Rect,
+    /// The number of columns and rows of cells the screen shows.
+    screen: (usize, usize),
+    /// The smallest size the grid is fitted to, so a pasted pattern larger than the screen can
+    /// be panned around rather than cut off.
+    min_grid_size: (usize, usize),
+    /// Langton's Ant walking over its own board in place of the grid, selected with `?mode=ant`.
+    ant: Option<LangtonAnt>,
+    /// Gliders spawning along the top edge, selected with `?mode=glider_rain`.
+    glider_rain: Option<GliderRain>,
+}
+
+impl App {
+    fn new(grid: Grid<CellState>) -> Self {
+        Self {
+            grid,
+            panels: Rect::default(),
+            simulation: Simulation::Running,
+            generation: 0,
+            generations_per_second: DEFAULT_GENERATIONS_PER_SECOND,
+            motion: MotionMode::Full,
+            cell_width: CellWidth::Single,
+            glyph: 0,
+            rain: false,
+            last_tick: None,
+            pending_ms: 0.0,
+            settled_generations: 0,
+            focused_link: None,
+            theme: 0,
+            theme_chosen: false,
+            hud: Hud::default(),
+            stroke: None,
+            stroke_painted: false,
+            toast: Rc::default(),
+            mask_panels: false,
+            cross_fade: None,
+            inspect: false,
+            hover: None,
+            help: false,
+            clusters: false,
+            spaceships: false,
+            found_spaceships: Vec::new(),
+            spaceships_found_at: None,
+            viewport: Point::new(0, 0),
+            minimap: Rect::default(),
+            screen: (0, 0),
+            min_grid_size: (0, 0),
+            ant: None,
+            glider_rain: None,
+        }
+    }
+
+    /// The board on screen, the ant's while it walks.
+    fn board(&self) -> &Grid<CellState> {
+        self.ant.as_ref().map_or(&self.grid, |ant| &ant.grid)
+    }
+
+    fn theme(&self) -> &'static Theme {
+        &THEMES[self.theme]
+    }
+
+    /// The glyphs cells are drawn with, the alive one picked with `a`.
+    fn glyphs(&self) -> CellGlyphs {
+        CellGlyphs {
+            alive: ALIVE_GLYPHS[self.glyph],
+            ..CELL_GLYPHS
+        }
+    }
+
+    fn cycle_glyph(&mut self) {
+        self.glyph = (self.glyph + 1) % ALIVE_GLYPHS.len();
+    }
+
+    fn choose_theme(&mut self, index: usize) {
+        self.theme = index;
+        self.theme_chosen = true;
+    }
+
+    /// Switches to the theme matching the browser's color scheme, unless one was chosen.
+    fn follow_color_scheme(&mut self, light: bool) {
+        if !self.theme_chosen {
+            self.theme = Theme::index_for_scheme(light);
+        }
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) {
+        if let Some(binding) = KEYBINDINGS.iter().find(|binding| binding.matches(&event)) {
+            self.perform(binding.action);
+        }
+    }
+
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::PanLeft => self.pan(-PAN_STEP, 0),
+            Action::PanRight => self.pan(PAN_STEP, 0),
+            Action::PanUp => self.pan(0, -PAN_STEP),
+            Action::PanDown => self.pan(0, PAN_STEP),
+            Action::TogglePause => self.simulation.toggle_pause(),
+            Action::Step => self.simulation.step(),
+            Action::StepBack => self.step_back(),
+            Action::SpeedUp => self.adjust_speed(1),
+            Action::SlowDown => self.adjust_speed(-1),
+            Action::Clear => self.clear(),
+            Action::Reseed => self.reseed(),
+            Action::GliderGun => {
+                self.grid
+                    .insert(&patterns::gosper_glider_gun(), GLIDER_GUN_POSITION);
+                self.spaceships_found_at = None;
+            }
+            Action::ToggleMotion => self.motion.toggle(),
+            Action::ToggleRain => self.rain = !self.rain,
+            Action::ToggleCellWidth => self.cell_width.toggle(),
+            Action::CycleGlyph => {
+                self.cycle_glyph();
+                web::storage_set(GLYPH_STORAGE_KEY, &self.glyphs().alive.to_string());
+            }
+            Action::ExportPng => export_png(&self.grid, self.theme()),
+            Action::CopyRle => self.copy_rle(),
+            Action::ToggleHud => self.hud.visible = !self.hud.visible,
+            Action::CycleRuleset => self.cycle_ruleset(),
+            Action::ToggleMask => self.mask_panels = !self.mask_panels,
+            Action::ToggleInspect => self.inspect = !self.inspect,
+            Action::ToggleHeat => self.grid.set_heat_tracking(self.grid.heat().is_none()),
+            Action::ToggleShimmer => {
+                let enabled = self.grid.birth_parity().is_none();
+                self.grid.set_birth_parity_tracking(enabled);
+            }
+            Action::ToggleClusters => self.clusters = !self.clusters,
+            Action::ToggleSpaceships => self.spaceships = !self.spaceships,
+            Action::NextTheme => self.choose_theme((self.theme + 1) % THEMES.len()),
+            Action::ToggleLight => {
+                let light = self.theme() == &theme::LIGHT;
+                self.choose_theme(Theme::index_for_scheme(!light));
+            }
+            Action::NextLink => self.move_link_focus(1),
+            Action::PreviousLink => self.move_link_focus(-1),
+            Action::Unfocus if self.help => self.help = false,
+            Action::Unfocus => self.focused_link = None,
+            Action::OpenLink => {
+                if let Some(index) = self.focused_link {
+                    let _ = open_url(LINKS[index].url, true);
+                }
+            }
+            Action::ToggleHelp => self.help = !self.help,
+        }
+    }
+
+    /// Changes the generations per second by `delta`, within `GENERATIONS_PER_SECOND`.
+    fn adjust_speed(&mut self, delta: i32) {
+        self.generations_per_second = self
+            .generations_per_second
+            .saturating_add_signed(delta)
+            .clamp(
+                *GENERATIONS_PER_SECOND.start(),
+                *GENERATIONS_PER_SECOND.end(),
+            );
+    }
+
+    /// Switches the grid to the one of `RULESETS` after its current ruleset, keeping its cells,
+    /// and names it in a toast. Rulesets which are not in the list, such as those of pasted
+    /// patterns, switch to the first one.
+    fn cycle_ruleset(&mut self) {
+        let current = self.grid.ruleset().unwrap_or_default();
+        let next = RULESETS
+            .iter()
+            .position(|named| named.ruleset == current)
+            .map_or(0, |index| (index + 1) % RULESETS.len());
+        let named = &RULESETS[next];
+        self.grid.set_ruleset(named.ruleset);
+        self.settled_generations = 0;
+        self.spaceships_found_at = None;
+        self.toast.set(Some(Toast::new(named.name)));
+    }
+
+    /// The name of the grid's ruleset, its B/S notation unless it is one of `RULESETS`.
+    fn rule_name(&self) -> String {
+        let ruleset = self.grid.ruleset().unwrap_or_default();
+        RULESETS
+            .iter()
+            .find(|named| named.ruleset == ruleset)
+            .map_or_else(|| ruleset.to_string(), |named| named.name.to_string())
+    }
+
+    /// Moves the link focus by `delta` links, wrapping around at either end.
+    ///
+    /// Without a focused link, moving forward focuses the first link and backward the last.
+    fn move_link_focus(&mut self, delta: isize) {
+        let count = LINKS.len() as isize;
+        let index = match self.focused_link {
+            Some(index) => (index as isize + delta).rem_euclid(count),
+            None if delta < 0 => count - 1,
+            None => 0,
+        };
+        self.focused_link = Some(index as usize);
+    }
+
+    /// The generations computed per second, taking reduced motion into account.
+    fn effective_generations_per_second(&self) -> u32 {
+        match self.motion {
+            MotionMode::Full => self.generations_per_second,
+            MotionMode::Reduced => self
+                .generations_per_second
+                .min(REDUCED_MOTION_GENERATIONS_PER_SECOND),
+        }
+    }
+
+    /// Resizes the grid to cover `area` when the window was resized, keeping it at least
+    /// `min_grid_size`.
+    fn fit(&mut self, area: Rect) {
+        // A last cell only half on screen still fills the final column
+        let columns = area.width.div_ceil(self.cell_width.columns());
+        self.screen = (columns.into(), area.height.into());
+        let width = self.screen.0.max(self.min_grid_size.0);
+        let height = self.screen.1.max(self.min_grid_size.1);
+        if (self.grid.width, self.grid.height) != (width, height) {
+            self.grid.resize(width, height);
+            self.cross_fade = None;
+            self.spaceships_found_at = None;
+        }
+        self.pan(0, 0);
+    }
+
+    /// Moves the view by `dx` columns and `dy` rows of cells, keeping it on the grid.
+    fn pan(&mut self, dx: i32, dy: i32) {
+        let max_x = self.grid.width.saturating_sub(self.screen.0) as i32;
+        let max_y = self.grid.height.saturating_sub(self.screen.1) as i32;
+        self.viewport = Point::new(
+            (self.viewport.x + dx).clamp(0, max_x),
+            (self.viewport.y + dy).clamp(0, max_y),
+        );
+    }
+
+    /// The spaceships on the grid, only searched for again when the board changed since the
+    /// last search, as `moving_components` evolves every cluster.
+    fn find_spaceships(&mut self) -> &[Vec<Point>] {
+        if self.spaceships_found_at != Some(self.generation) {
+            self.found_spaceships = self.grid.moving_components(SPACESHIP_MAX_PERIOD);
+            self.spaceships_found_at = Some(self.generation);
+        }
+        &self.found_spaceships
+    }
+
+    /// Moves the view so `p` is in the middle of the screen, as far as the grid allows.
+    fn center_on(&mut self, p: Point) {
+        self.viewport = Point::new(
+            p.x - self.screen.0 as i32 / 2,
+            p.y - self.screen.1 as i32 / 2,
+        );
+        self.pan(0, 0);
+    }
+
+    /// Whether only part of the grid fits on screen, so the minimap is shown to find the way
+    /// around it.
+    fn zoomed_in(&self) -> bool {
+        self.ant.is_none() && (self.grid.width > self.screen.0 || self.grid.height > self.screen.1)
+    }
+
+    /// The grid cell in the middle of the minimap block at `position`, if there is one.
+    fn minimap_cell(&self, position: Position) -> Option<Point> {
+        let inner = self.minimap.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            return None;
+        }
+        let scale = minimap_scale(self.grid.width, self.grid.height);
+        let block = |offset: u16| (usize::from(offset) * scale + scale / 2) as i32;
+        Some(Point::new(
+            block(position.x - inner.x),
+            block(position.y - inner.y),
+        ))
+    }
+
+    /// Computes the generations due by `now`, in milliseconds since the page loaded, returning
+    /// whether any were.
+    ///
+    /// While running, generations are computed at `generations_per_second` of wall-clock time
+    /// however often frames are rendered, so the board evolves at the same speed on every
+    /// display. At most `MAX_FRAME_MS` and `MAX_GENERATIONS_PER_FRAME` are caught up on per
+    /// frame, time beyond that being dropped.
+    fn tick(&mut self, now: f64) -> bool {
+        let elapsed = self
+            .last_tick
+            .map_or(0.0, |last| (now - last).clamp(0.0, MAX_FRAME_MS));
+        self.last_tick = Some(now);
+        match self.simulation {
+            Simulation::Running => {
+                let interval = 1000.0 / f64::from(self.effective_generations_per_second());
+                self.pending_ms += elapsed;
+                let mut generations = 0;
+                while self.pending_ms >= interval {
+                    if generations == MAX_GENERATIONS_PER_FRAME {
+                        self.pending_ms = 0.0;
+                        break;
+                    }
+                    self.pending_ms -= interval;
+                    self.advance_generation();
+                    generations += 1;
+                }
+                generations > 0
+            }
+            Simulation::Paused | Simulation::Step => {
+                self.pending_ms = 0.0;
+                let advanced = self.simulation.advance();
+                if advanced {
+                    self.advance_generation();
+                }
+                advanced
+            }
+        }
+    }
+
+    /// Forgets the time of the last frame, for when the page is hidden and no frames are
+    /// rendered until it is shown again.
+    fn reset_clock(&mut self) {
+        self.last_tick = None;
+        self.pending_ms = 0.0;
+    }
+
+    /// Computes the next generation, reseeding the grid once it has been settled for long
+    /// unless gliders rain on it.
+    fn advance_generation(&mut self) {
+        if let Some(ant) = &mut self.ant {
+            ant.step();
+            self.generation += 1;
+            return;
+        }
+        self.grid.update_states();
+        if self.rain {
+            self.grid.sprinkle(RAIN_PROBABILITY);
+        }
+        self.generation += 1;
+        if let Some(rain) = &mut self.glider_rain {
+            rain.spawn(&mut self.grid, self.generation);
+            return;
+        }
+        self.settled_generations = match self.grid.status() {
+            GridStatus::Evolving => 0,
+            GridStatus::Still | GridStatus::Oscillating => self.settled_generations + 1,
+        };
+        if self.settled_generations >= RESEED_AFTER_SETTLED {
+            self.reseed();
+        }
+    }
+
+    /// Fills the grid with new random cells, keeping its settings, and restarts the ant.
+    /// Unless motion is reduced, the old cells fade out while the new ones fade in.
+    fn reseed(&mut self) {
+        let alive = self.glyphs().alive;
+        self.cross_fade = (self.motion == MotionMode::Full)
+            .then(|| CrossFade::new(self.grid.cells.clone(), alive));
+        self.grid.randomize(RESEED_DENSITY);
+        self.restart_ant();
+        self.generation = 0;
+        self.settled_generations = 0;
+        self.spaceships_found_at = None;
+    }
+
+    /// Puts the ant, if walking, back in the middle of an empty board.
+    fn restart_ant(&mut self) {
+        if let Some(ant) = &mut self.ant {
+            *ant = LangtonAnt::new(self.grid.width, self.grid.height);
+        }
+    }
+
+    /// Pauses and goes back a generation, if one is kept.
+    fn step_back(&mut self) {
+        self.simulation = Simulation::Paused;
+        if self.grid.step_back() {
+            self.generation = self.generation.saturating_sub(1);
+            self.settled_generations = 0;
+            self.spaceships_found_at = None;
+        }
+    }
+
+    /// Kills every cell, restarts the ant and pauses, so the board can be drawn on from scratch.
+    fn clear(&mut self) {
+        self.grid.clear();
+        self.cross_fade = None;
+        self.restart_ant();
+        self.generation = 0;
+        self.settled_generations = 0;
+        self.spaceships_found_at = None;
+        self.simulation = Simulation::Paused;
+    }
+
+    /// Replaces the board with the pattern in `text`, RLE or plaintext, centered and paused so
+    /// it can be looked at before it evolves. Shows a toast if it cannot be read.
+    fn paste(&mut self, text: &str) {
+        let Some(pattern) = parse_pattern(text) else {
+            self.toast
+                .set(Some(Toast::new("Could not read the pasted pattern")));
+            return;
+        };
+        self.clear();
+        // Patterns without a rule are meant for Conway's rules
+        self.grid.set_ruleset(pattern.ruleset().unwrap_or_default());
+        // Patterns larger than the screen get a grid they fit on, viewed from their middle
+        self.min_grid_size = (pattern.width, pattern.height);
+        let width = self.grid.width.max(pattern.width);
+        let height = self.grid.height.max(pattern.height);
+        self.grid.resize(width, height);
+        self.viewport = Point::new(
+            (width.saturating_sub(self.screen.0) / 2) as i32,
+            (height.saturating_sub(self.screen.1) / 2) as i32,
+        );
+        let centered = pattern.centered_in(self.grid.width, self.grid.height);
+        self.grid.insert(&centered, Point::new(0, 0));
+        self.spaceships_found_at = None;
+        self.toast.set(Some(Toast::new("Pasted!")));
+    }
+
+    /// A short summary of the simulation for the status line.
+    fn status(&self) -> String {
+        let mut status = format!(
+            "|generation {}|population {}|speed {}/s|",
+            self.generation,
+            self.board().population(),
+            self.effective_generations_per_second()
+        );
+        if self.motion == MotionMode::Reduced {
+            status.push_str("reduced motion|");
+        }
+        if self.rain {
+            status.push_str("rain|");
+        }
+        if let Some(toast) = self.toast.get() {
+            status.push_str(toast.text);
+            status.push('|');
+        }
+        status
+    }
+
+    /// Copies the live cells to the clipboard as RLE, showing a toast once done.
+    fn copy_rle(&self) {
+        let Some(rle) = live_rle(&self.grid) else {
+            self.toast.set(Some(Toast::new("Nothing to copy")));
+            return;
+        };
+        let toast = self.toast.clone();
+        web::copy_to_clipboard(&rle, move |copied| {
+            let text = if copied { "Copied!" } else { "Copy failed" };
+            toast.set(Some(Toast::new(text)));
+        });
+    }
+
+    /// Counts down the frames of the cross-fade, ending it once they run out.
+    fn advance_cross_fade(&mut self) {
+        self.cross_fade = self.cross_fade.take().and_then(|fade| {
+            let frames_left = fade.frames_left.checked_sub(1)?;
+            Some(CrossFade {
+                frames_left,
+                ..fade
+            })
+        });
+    }
+
+    /// Counts down the frames the toast is shown for, hiding it once they run out.
+    fn expire_toast(&self) {
+        let toast = self.toast.get().and_then(|toast| {
+            let frames_left = toast.frames_left.checked_sub(1)?;
+            Some(Toast {
+                frames_left,
+                ..toast
+            })
+        });
+        self.toast.set(toast);
+    }
+
+    /// The grid cell rendered at `position`, `None` if it is hidden behind the panels or the
+    /// ant's board is shown instead, which is left to the ant.
+    fn cell_at(&self, position: Position) -> Option<Point> {
+        if self.panels.contains(position) || self.minimap.contains(position) || self.ant.is_some() {
+            return None;
+        }
+        let x = position.x / self.cell_width.columns();
+        Some(Point::new(
+            self.viewport.x + i32::from(x),
+            self.viewport.y + i32::from(position.y),
+        ))
+    }
+
+    /// Describes the cell under the pointer while inspecting: its point, its state and how many
+    /// of its neighbours are alive. `None` over the panels, where there is no cell to describe.
+    fn hover_text(&self) -> Option<String> {
+        if !self.inspect {
+            return None;
+        }
+        let p = self.cell_at(self.hover?)?;
+        let state = match self.grid.try_get(p)? {
+            CellState::Alive(_) | CellState::AliveB(_) => "alive",
+            CellState::Dying(_) => "dying",
+            CellState::Dead(_) => "dead",
+        };
+        let neighbours = self.grid.alive_neighbours(p);
+        Some(format!(
+            "({}, {}) {state}, {neighbours} alive neighbours",
+            p.x, p.y
+        ))
+    }
+
+    /// Toggles the grid cell at `position` unless it is hidden behind the panels, or the click
+    /// ends a stroke. Clicks on the minimap move the view to the part of the grid clicked.
+    fn click(&mut self, position: Position) {
+        if std::mem::take(&mut self.stroke_painted) {
+            return;
+        }
+        if let Some(p) = self.minimap_cell(position) {
+            self.center_on(p);
+        } else if let Some(p) = self.cell_at(position) {
+            self.grid.toggle_cell(p);
+            self.spaceships_found_at = None;
+        }
+    }
+
+    /// Handles the mouse for painting strokes of cells, or filling selected rectangles, while
+    /// paused. Nothing changes until the pointer moves on from the pressed cell, so a plain click
+    /// still toggles it.
+    fn mouse(&mut self, action: CellMouse) {
+        if let CellMouse::Down { position, .. } | CellMouse::Move(position) = action {
+            self.hover = Some(position);
+        }
+        match action {
+            CellMouse::Down {
+                position,
+                erase,
+                select,
+            } => {
+                self.stroke = (self.simulation == Simulation::Paused)
+                    .then(|| self.cell_at(position))
+                    .flatten()
+                    .map(|start| Stroke {
+                        start,
+                        last: start,
+                        erase,
+                        select,
+                    });
+            }
+            CellMouse::Move(position) => {
+                let (Some(stroke), Some(p)) = (self.stroke, self.cell_at(position)) else {
+                    return;
+                };
+                if p == stroke.last {
+                    return;
+                }
+                if !stroke.select {
+                    let state = stroke.state(&self.grid);
+                    for point in line(stroke.last, p) {
+                        self.grid.set_cell(point, state);
+                    }
+                    self.spaceships_found_at = None;
+                }
+                self.stroke = Some(Stroke { last: p, ..stroke });
+                self.stroke_painted = true;
+            }
+            CellMouse::Up => {
+                if let Some(stroke) = self.stroke.take() {
+                    if stroke.select && stroke.last != stroke.start {
+                        let state = stroke.state(&self.grid);
+                        self.grid.fill_region(stroke.start, stroke.last, state);
+                        self.spaceships_found_at = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sets up the terminal the site is drawn in and measures it.
+fn init_terminal() -> io::Result<(Terminal<DomBackend>, Size)> {
+    let terminal = Terminal::new(DomBackend::new()?)?;
+    let size = terminal.size()?;
+    Ok((terminal, size))
+}
+
+#[cfg(not(feature = "native"))]
+fn main() -> io::Result<()> {
+    run_in_browser()
+}
+
+#[cfg(feature = "native")]
+fn main() -> io::Result<()> {
+    native::run()
+}
+
+/// Turns on the tracking the background relies on: cell ages for its colors and past
+/// generations for stepping back.
+fn prepare_background(grid: &mut Grid<CellState>) {
+    grid.set_age_tracking(true);
+    grid.set_history_capacity(HISTORY_LENGTH);
+}
+
+/// Runs the site in the browser. It is built with the `native` feature as well so it keeps
+/// compiling, but only `native::run` is called then.
+#[cfg_attr(feature = "native", allow(dead_code))]
+fn run_in_browser() -> io::Result<()> {
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+    let (terminal, size) = match init_terminal() {
+        Ok(initialized) => initialized,
+        Err(err) => {
+            // Rather than leave visitors staring at a blank page
+            web::show_fatal_error(TERMINAL_FAILED_MESSAGE, &err.to_string());
+            return Ok(());
+        }
+    };
+    web::remove_element(SPLASH_ID);
+    let (width, height) = (size.width.into(), size.height.into());
+    // A shared `?pattern=` or `?seed=` link takes precedence over the board saved on a previous
+    // visit, unknown pattern names falling back to the seed or the saved board
+    let pattern = web::query_param("pattern").and_then(|name| patterns::by_name(&name));
+    let seed = web::query_param("seed").and_then(|seed| seed.parse().ok());
+    let mut grid = match (pattern, seed) {
+        (Some(pattern), _) => pattern.centered_in(width, height),
+        (None, Some(seed)) => GridBuilder::new()
+            .width(width)
+            .height(height)
+            .seed(seed)
+            .build(),
+        (None, None) => web::storage_get(BOARD_STORAGE_KEY)
+            .and_then(|saved| decode_board(&saved, width, height))
+            .unwrap_or_else(|| {
+                Grid::new_random_symmetric(width, height, Symmetry::Both, RESEED_DENSITY)
+            }),
+    };
+    prepare_background(&mut grid);
+    let meetups = upcoming_meetups(MEETUPS, &web::today());
+    let mut app = App::new(grid);
+    match web::query_param("mode").as_deref() {
+        Some("ant") => app.ant = Some(LangtonAnt::new(width, height)),
+        Some("glider_rain") => {
+            let interval = web::query_param("rain_interval")
+                .and_then(|interval| interval.parse().ok())
+                .unwrap_or(GLIDER_RAIN_INTERVAL);
+            let density = web::query_param("rain_density")
+                .and_then(|density| density.parse().ok())
+                .unwrap_or(GLIDER_RAIN_DENSITY);
+            app.grid.clear();
+            app.glider_rain = Some(GliderRain::new(
+                interval,
+                density,
+                seed.unwrap_or(GLIDER_RAIN_SEED),
+            ));
+        }
+        _ => {}
+    }
+    if web::prefers_reduced_motion() {
+        app.motion = MotionMode::Reduced;
+    }
+    app.follow_color_scheme(web::prefers_light_scheme());
+    if let Some(index) = web::storage_get(GLYPH_STORAGE_KEY).and_then(|saved| {
+        ALIVE_GLYPHS
+            .iter()
+            .position(|glyph| saved == glyph.to_string())
+    }) {
+        app.glyph = index;
+    }
+    if let Some(index) = web::query_param("theme").and_then(|name| Theme::index_by_name(&name)) {
+        app.choose_theme(index);
+    }
+    let app = Rc::new(RefCell::new(app));
+
+    web::on_cell_click({
+        let app = app.clone();
+        move |position| app.borrow_mut().click(position)
+    });
+    web::on_cell_mouse({
+        let app = app.clone();
+        move |action| app.borrow_mut().mouse(action)
+    });
+    web::on_page_hide({
+        let app = app.clone();
+        move || web::storage_set(BOARD_STORAGE_KEY, &encode_board(&app.borrow().grid))
+    });
+    web::on_page_hide({
+        let app = app.clone();
+        move || app.borrow_mut().reset_clock()
+    });
+    web::on_color_scheme_change({
+        let app = app.clone();
+        move |light| app.borrow_mut().follow_color_scheme(light)
+    });
+    web::on_paste({
+        let app = app.clone();
+        move |text| app.borrow_mut().paste(&text)
+    });
+    web::prevent_tab_focus();
+    terminal.on_key_event({
+        let app = app.clone();
+        move |event| app.borrow_mut().handle_key(event)
+    });
+
+    terminal.draw_web(move |frame| {
+        let mut app = app.borrow_mut();
+        app.fit(frame.area());
+        app.expire_toast();
+        app.advance_cross_fade();
+        let start = web::now();
+        app.hud.record_frame(start);
+        if app.tick(start) {
+            app.hud.update_ms = web::now() - start;
+        }
+        render_app(frame, &mut app, &meetups, is_mobile());
+    });
+
+    Ok(())
+}
+
+/// Renders the site: the background grid, the content panels on top and the overlays.
+///
+/// Only draws on the `frame` and updates `app` for what was drawn, so it works on any ratatui
+/// backend and not only in the browser.
+fn render_app(frame: &mut Frame<'_>, app: &mut App, meetups: &[&Meetup], mobile: bool) {
+    let theme = app.theme();
+    if app.spaceships && app.ant.is_none() {
+        app.find_spaceships();
+    }
+    // The panels are laid out after the grid is drawn, so the mask is where they were on the
+    // previous frame, which is where they still are unless the window was resized
+    let mask = if app.mask_panels {
+        std::slice::from_ref(&app.panels)
+    } else {
+        &[]
+    };
+    render_game_of_life(
+        app.board(),
+        frame,
+        theme,
+        app.glyphs(),
+        app.cell_width,
+        app.viewport,
+        mask,
+    );
+    if app.clusters && app.ant.is_none() {
+        render_clusters(&app.grid, frame, app.cell_width, app.viewport, mask);
+    }
+    if app.spaceships && app.ant.is_none() {
+        render_spaceships(
+            &app.found_spaceships,
+            frame,
+            app.cell_width,
+            app.viewport,
+            mask,
+        );
+    }
+    if let (Some(fade), None) = (&app.cross_fade, &app.ant) {
+        render_cross_fade(
+            &app.grid,
+            fade,
+            frame,
+            theme,
+            app.cell_width,
+            app.viewport,
+            mask,
+        );
+    }
+    if let Some(ant) = &app.ant {
+        render_ant(ant, frame, theme, app.cell_width, app.viewport);
+    }
+
+    let (vert_perc, hori_perc) = content_percentages(frame.area().width, mobile);
+
+    let vertical = Layout::vertical([Constraint::Percentage(vert_perc)]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([Constraint::Percentage(hori_perc)]).flex(Flex::Center);
+    let [area] = vertical.areas(frame.area());
+    let [area] = horizontal.areas(area);
+
+    app.panels = if mobile {
+        render_mobile(area, frame, meetups, app.focused_link, theme)
+    } else {
+        render_desktop(area, frame, meetups, app.focused_link, theme)
+    };
+    render_status(frame, app.panels, app.status());
+    app.minimap = if app.zoomed_in() {
+        render_minimap(frame, &app.grid, app.viewport, app.screen, theme)
+    } else {
+        Rect::default()
+    };
+    if app.hud.visible {
+        render_hud(
+            frame,
+            &app.hud.text(app.board().population(), &app.rule_name()),
+        );
+    }
+    if let (Some(position), Some(text)) = (app.hover, app.hover_text()) {
+        render_tooltip(frame, position, &text);
+    }
+    if app.help {
+        render_help(frame, theme);
+    }
+}
+
+/// Paints the alive cells of `grid` in the colors of `theme` onto an offscreen canvas, a fixed
+/// number of pixels per cell so the image stays crisp, and downloads it as a PNG.
+fn export_png(grid: &Grid<CellState>, theme: &Theme) {
+    let (width, height) = (grid.width as u32, grid.height as u32);
+    let Some((canvas, context)) =
+        web::offscreen_canvas(width * EXPORT_CELL_PIXELS, height * EXPORT_CELL_PIXELS)
+    else {
+        web::warn("Exporting images is not supported by this browser");
+        return;
+    };
+    let size = f64::from(EXPORT_CELL_PIXELS);
+    context.set_fill_style_str(&css_color(theme.background));
+    context.fill_rect(
+        0.0,
+        0.0,
+        f64::from(canvas.width()),
+        f64::from(canvas.height()),
+    );
+    context.set_fill_style_str(&css_color(theme.text));
+    for p in grid.alive_points() {
+        context.fill_rect(f64::from(p.x) * size, f64::from(p.y) * size, size, size);
+    }
+    web::download_png(&canvas, EXPORT_FILENAME);
+}
+
+/// Formats `color` for CSS, colors of the terminal palette becoming black.
+fn css_color(color: Color) -> String {
+    match color {
+        Color::Rgb(red, green, blue) => format!("#{red:02x}{green:02x}{blue:02x}"),
+        _ => "#000000".to_string(),
+    }
+}
+
+/// The RLE of the smallest part of `grid` holding all of its alive cells, `None` if there are
+/// none.
+fn live_rle(grid: &Grid<CellState>) -> Option<String> {
+    let (min, max) = grid.live_bounds()?;
+    let (width, height) = ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+    Some(grid.crop(min, width, height).to_rle())
+}
+
+/// The points on the straight line from `from` to `to`, both included, by Bresenham's
+/// algorithm so fast drags leave no gaps.
+fn line(from: Point, to: Point) -> Vec<Point> {
+    let (dx, dy) = ((to.x - from.x).abs(), -(to.y - from.y).abs());
+    let (step_x, step_y) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+    let mut error = dx + dy;
+    let mut p = from;
+    let mut points = vec![p];
+    while p != to {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            p.x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            p.y += step_y;
+        }
+        points.push(p);
+    }
+    points
+}
+
+/// Reads a pattern from `text` as RLE, or as plaintext if it is not RLE.
+fn parse_pattern(text: &str) -> Option<Grid<CellState>> {
+    Grid::from_rle(text)
+        .or_else(|_| Grid::from_plaintext(text))
+        .ok()
+}
+
+fn encode_board(grid: &Grid<CellState>) -> String {
+    format!("{BOARD_FORMAT}{}", grid.to_rle())
+}
+
+/// Restores a board saved by `encode_board` into a grid of `width` by `height`,
+/// `None` if it was saved in another format or is corrupt.
+fn decode_board(saved: &str, width: usize, height: usize) -> Option<Grid<CellState>> {
+    let saved = Grid::from_rle(saved.strip_prefix(BOARD_FORMAT)?).ok()?;
+    if saved.width == width && saved.height == height {
+        return Some(saved);
+    }
+    // The window was resized since the board was saved
+    let mut grid = Grid::new_empty(width, height);
+    if let Some(ruleset) = saved.ruleset() {
+        grid.set_ruleset(ruleset);
+    }
+    grid.insert(&saved, Point::new(0, 0));
+    Some(grid)
+}
+
+/// Returns the meetups taking place `today` or later, `today` being formatted as `YYYY-MM-DD`.
+fn upcoming_meetups<'a>(meetups: &'a [Meetup], today: &str) -> Vec<&'a Meetup> {
+    meetups
+        .iter()
+        .filter(|meetup| meetup.date >= today)
+        .collect()
+}
+
+/// Returns the percentages of the screen height and width covered by the content panels,
+/// widening them on narrow screens so they are not squeezed.
+fn content_percentages(width: u16, mobile: bool) -> (u16, u16) {
+    let (vertical, horizontal) = if mobile { (30, 80) } else { (80, 60) };
+    if width < NARROW_WIDTH {
+        (vertical, 90)
+    } else {
+        (vertical, horizontal)
+    }
+}
+
+/// Returns the banner to show in panels `width` columns wide, the compact one when the full
+/// banner would overflow.
+fn banner(width: u16) -> &'static str {
+    let banner_width = BANNER.lines().map(|line| line.chars().count()).max();
+    if banner_width.unwrap_or(0) <= usize::from(width) {
+        BANNER
+    } else {
+        COMPACT_BANNER
+    }
+}
+
+/// The width the description is wrapped to inside panels `width` columns wide.
+fn description_width(width: u16) -> usize {
+    usize::from(width.saturating_sub(DESCRIPTION_MARGIN)).max(MIN_DESCRIPTION_WIDTH)
+}
+
+/// The height of the Meetups panel listing `count` meetups, borders included.
+fn meetups_height(count: usize) -> u16 {
+    count.clamp(1, MAX_MEETUP_LINES) as u16 + 2
+}
+
+fn render_mobile(
+    area: Rect,
+    frame: &mut Frame,
+    meetups: &[&Meetup],
+    focused_link: Option<usize>,
+    theme: &Theme,
+) -> Rect {
+    let constraints = [
+        Constraint::Length(meetups_height(meetups.len())),
+        Constraint::Length(LINKS.len() as u16 + 2),
+    ];
+    let background = render_background(
+        frame,
+        area,
+        Some("Terminal Collective".to_string()),
+        &constraints,
+        theme,
+    );
+    let [meetups_area, links_area] = Layout::vertical(constraints).areas(area);
+    render_meetups(frame, meetups_area, meetups);
+    render_links(frame, links_area, focused_link);
+    background
+}
+
+fn render_desktop(
+    area: Rect,
+    frame: &mut Frame,
+    meetups: &[&Meetup],
+    focused_link: Option<usize>,
+    theme: &Theme,
+) -> Rect {
+    let banner = banner(area.width);
+    let description = textwrap::wrap(DESCRIPTION.trim(), description_width(area.width))
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+    let constraints = [
+        Constraint::Length(banner.lines().count() as u16 + 1),
+        Constraint::Length(description.lines().count() as u16 + 2),
+        Constraint::Length(meetups_height(meetups.len())),
+        Constraint::Length(LINKS.len() as u16 + 2),
+    ];
+    let background = render_background(frame, area, None, &constraints, theme);
+    let [banner_area, description_area, meetups_area, links_area] =
+        Layout::vertical(constraints).areas(area);
+    render_banner(frame, banner, banner_area);
+    render_description(frame, description, description_area);
+    render_meetups(frame, meetups_area, meetups);
+    render_links(frame, links_area, focused_link);
+    background
+}
+
+/// Renders the grid straight into the frame buffer, without building any text first.
+///
+/// With a decay or age tracking, alive cells are colored by their age and dying cells get
+/// progressively darker as they fade. Cells of the second color in two color Life are always
+/// colored. With heat tracking, the background behind cells glows where they were recently
+/// alive. Only the cells from `viewport` on which fit on screen are drawn, and none inside the
+/// `mask`.
+fn render_game_of_life(
+    grid: &Grid<CellState>,
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    glyphs: CellGlyphs,
+    cell_width: CellWidth,
+    viewport: Point,
+    mask: &[Rect],
+) {
+    let area = frame.area();
+    let buffer = frame.buffer_mut();
+    buffer.set_style(area, Style::default().fg(theme.cell).bg(theme.page));
+    let colored = grid.decay() > 0 || grid.ages().is_some();
+    let width = grid.width.max(1);
+    let (left, top) = (viewport.x.max(0) as usize, viewport.y.max(0) as usize);
+    for (y, row) in grid
+        .cells
+        .chunks(width)
+        .skip(top)
+        .enumerate()
+        .take(area.height.into())
+    {
+        let columns = cell_width.columns();
+        for (x, cell) in row
+            .iter()
+            .skip(left)
+            .enumerate()
+            .take(area.width.div_ceil(columns).into())
+        {
+            let idx = (top + y) * width + left + x;
+            let shimmer = grid.birth_parity().is_some_and(|parity| parity[idx]);
+            let fg = (colored || shimmer || matches!(cell, CellState::AliveB(_))).then(|| {
+                let age = grid.ages().map(|ages| ages[idx]);
+                let color = cell_color(cell, age, grid.decay(), theme);
+                if shimmer {
+                    let (red, green, blue) = theme.fade;
+                    mix_color(color, Color::Rgb(red, green, blue), SHIMMER_SHADE)
+                } else {
+                    color
+                }
+            });
+            let bg = grid
+                .heat()
+                .and_then(|heat| heat_color(heat[idx], grid.heat_decay()));
+            for column in 0..columns {
+                let position =
+                    Position::new(area.x + x as u16 * columns + column, area.y + y as u16);
+                if mask.iter().any(|rect| rect.contains(position)) {
+                    continue;
+                }
+                let Some(target) = buffer.cell_mut(position) else {
+                    continue;
+                };
+                target.set_char(cell.glyph(glyphs));
+                if let Some(fg) = fg {
+                    target.set_fg(fg);
+                }
+                if let Some(bg) = bg {
+                    target.set_bg(bg);
+                }
+            }
+        }
+    }
+}
+
+/// Draws the cells which died or were born in a reseed over the rendered grid, the old ones
+/// fading from the cell color into the fade color of `theme` and the new ones the other way.
+fn render_cross_fade(
+    grid: &Grid<CellState>,
+    fade: &CrossFade,
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    cell_width: CellWidth,
+    viewport: Point,
+    mask: &[Rect],
+) {
+    if fade.old.len() != grid.cells.len() {
+        return;
+    }
+    let progress = fade.progress();
+    let (red, green, blue) = theme.fade;
+    let faded = Color::Rgb(red, green, blue);
+    let columns = cell_width.columns();
+    let area = frame.area();
+    let width = grid.width.max(1);
+    for (idx, (old, new)) in fade.old.iter().zip(&grid.cells).enumerate() {
+        let fading_out = match (old.is_alive(), new.is_alive()) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => continue,
+        };
+        let (Ok(x), Ok(y)) = (
+            u16::try_from(idx as i32 % width as i32 - viewport.x),
+            u16::try_from(idx as i32 / width as i32 - viewport.y),
+        ) else {
+            continue;
+        };
+        for column in 0..columns {
+            let position = Position::new(area.x + x * columns + column, area.y + y);
+            if mask.iter().any(|rect| rect.contains(position)) {
+                continue;
+            }
+            let Some(target) = frame.buffer_mut().cell_mut(position) else {
+                continue;
+            };
+            let fg = if fading_out {
+                target.set_char(fade.alive);
+                mix_color(theme.cell, faded, progress)
+            } else {
+                mix_color(faded, target.fg, progress)
+            };
+            target.set_fg(fg);
+        }
+    }
+}
+
+/// Colors each cluster of touching alive cells of `grid` with one of `CLUSTER_COLORS`, in the
+/// order the clusters are found.
+fn render_clusters(
+    grid: &Grid<CellState>,
+    frame: &mut Frame<'_>,
+    cell_width: CellWidth,
+    viewport: Point,
+    mask: &[Rect],
+) {
+    let colors = CLUSTER_COLORS.iter().cycle();
+    for (component, &(red, green, blue)) in grid.connected_components().iter().zip(colors) {
+        let color = Color::Rgb(red, green, blue);
+        paint_cells(frame, component, color, cell_width, viewport, mask);
+    }
+}
+
+/// Highlights the cells of `ships` in `SPACESHIP_COLOR`.
+fn render_spaceships(
+    ships: &[Vec<Point>],
+    frame: &mut Frame<'_>,
+    cell_width: CellWidth,
+    viewport: Point,
+    mask: &[Rect],
+) {
+    for ship in ships {
+        paint_cells(frame, ship, SPACESHIP_COLOR, cell_width, viewport, mask);
+    }
+}
+
+/// Recolors the rendered grid cells at `points` with `color`, leaving out those under `mask`.
+fn paint_cells(
+    frame: &mut Frame<'_>,
+    points: &[Point],
+    color: Color,
+    cell_width: CellWidth,
+    viewport: Point,
+    mask: &[Rect],
+) {
+    let columns = cell_width.columns();
+    let area = frame.area();
+    for p in points {
+        let (Ok(x), Ok(y)) = (
+            u16::try_from(p.x - viewport.x),
+            u16::try_from(p.y - viewport.y),
+        ) else {
+            continue;
+        };
+        for column in 0..columns {
+            let position = Position::new(area.x + x * columns + column, area.y + y);
+            if mask.iter().any(|rect| rect.contains(position)) {
+                continue;
+            }
+            if let Some(target) = frame.buffer_mut().cell_mut(position) {
+                target.set_fg(color);
+            }
+        }
+    }
+}
+
+/// The color `t` of the way from `from` to `to`, or `to` unless both are RGB colors.
+fn mix_color(from: Color, to: Color, t: f32) -> Color {
+    let (Color::Rgb(from_red, from_green, from_blue), Color::Rgb(to_red, to_green, to_blue)) =
+        (from, to)
+    else {
+        return to;
+    };
+    let mix = |from: u8, to: u8| (f32::from(from) + (f32::from(to) - f32::from(from)) * t) as u8;
+    Color::Rgb(
+        mix(from_red, to_red),
+        mix(from_green, to_green),
+        mix(from_blue, to_blue),
+    )
+}
+
+/// Draws Langton's Ant as an arrow pointing where it is heading, in the border color so it
+/// stands out from the cells.
+fn render_ant(
+    ant: &LangtonAnt,
+    frame: &mut Frame<'_>,
+    theme: &Theme,
+    cell_width: CellWidth,
+    viewport: Point,
+) {
+    let columns = cell_width.columns();
+    let position = ant.position();
+    let (Ok(x), Ok(y)) = (
+        u16::try_from(position.x - viewport.x),
+        u16::try_from(position.y - viewport.y),
+    ) else {
+        return;
+    };
+    let arrow = match ant.heading() {
+        Heading::North => '▲',
+        Heading::East => '▶',
+        Heading::South => '▼',
+        Heading::West => '◀',
+    };
+    let area = frame.area();
+    for column in 0..columns {
+        let x = area.x + x * columns + column;
+        let y = area.y + y;
+        if let Some(target) = frame.buffer_mut().cell_mut(Position::new(x, y)) {
+            target.set_char(arrow).set_fg(theme.border);
+        }
+    }
+}
+
+fn cell_color(cell: &CellState, age: Option<u16>, decay: u8, theme: &Theme) -> Color {
+    match (cell, age) {
+        (CellState::Dying(level), _) => {
+            let Color::Rgb(red, green, blue) = theme.cell else {
+                return theme.cell;
+            };
+            let (fade_red, fade_green, fade_blue) = theme.fade;
+            // Moves from the fade color at the end of the decay towards the alive cell color
+            let shade = |fade: u8, alive: u8| {
+                let (fade, alive) = (i32::from(fade), i32::from(alive));
+                (fade + (alive - fade) * i32::from(*level) / (i32::from(decay) + 1)) as u8
+            };
+            Color::Rgb(
+                shade(fade_red, red),
+                shade(fade_green, green),
+                shade(fade_blue, blue),
+            )
+        }
+        (CellState::AliveB(_), _) => theme.cell_b,
+        (CellState::Alive(_), Some(age)) => age_color(age, theme.age_gradient),
+        _ => theme.cell,
+    }
+}
+
+/// Maps the heat of a cell onto a warm gradient from dark red to yellow, reached by cells
+/// alive for long. `None` for cells too cold to show.
+fn heat_color(heat: f32, decay: f32) -> Option<Color> {
+    // The heat of a cell alive every generation tends to `1 / (1 - decay)`
+    let t = (heat * (1.0 - decay)).clamp(0.0, 1.0);
+    if t < MIN_VISIBLE_HEAT {
+        return None;
+    }
+    let [red, orange, yellow] = HEAT_GRADIENT;
+    let (from, to, t) = if t < 0.5 {
+        (red, orange, t * 2.0)
+    } else {
+        (orange, yellow, (t - 0.5) * 2.0)
+    };
+    let mix = |from: u8, to: u8| (f32::from(from) + (f32::from(to) - f32::from(from)) * t) as u8;
+    Some(Color::Rgb(
+        mix(from.0, to.0),
+        mix(from.1, to.1),
+        mix(from.2, to.2),
+    ))
+}
+
+/// Maps the age of a cell onto a three color `gradient`, by default green to teal to blue with
+/// newborn cells being green.
+fn age_color(age: u16, gradient: [(u8, u8, u8); 3]) -> Color {
+    let [green, teal, blue] = gradient;
+    let t = f32::from(age.min(AGE_GRADIENT_SPAN)) / f32::from(AGE_GRADIENT_SPAN);
+    let (from, to, t) = if t < 0.5 {
+        (green, teal, t * 2.0)
+    } else {
+        (teal, blue, (t - 0.5) * 2.0)
+    };
+    let mix = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+    Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+fn render_status(frame: &mut Frame<'_>, panels: Rect, status: String) {
+    let status = Line::from(status);
+    let area = Rect::new(
+        panels.x + 1,
+        panels.bottom().saturating_sub(1),
+        (status.width() as u16).min(panels.width.saturating_sub(2)),
+        1,
+    );
+    frame.render_widget(status, area.clamp(frame.area()));
+}
+
+/// Renders the performance overlay in the top left corner, away from the panels.
+fn render_hud(frame: &mut Frame<'_>, text: &str) {
+    let width = text.lines().map(str::len).max().unwrap_or(0) as u16 + 2;
+    let height = text.lines().count() as u16 + 2;
+    let area = Rect::new(0, 0, width, height).clamp(frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(text).block(Block::bordered()), area);
+}
+
+/// How many cells across and down each block of the minimap covers, the same both ways so the
+/// board keeps its shape, and as few as fit a `width` by `height` grid in `MINIMAP_SIZE`.
+fn minimap_scale(width: usize, height: usize) -> usize {
+    width
+        .div_ceil(MINIMAP_SIZE.0)
+        .max(height.div_ceil(MINIMAP_SIZE.1))
+        .max(1)
+}
+
+/// Shrinks `grid` into blocks of `scale` by `scale` cells, row by row, a block being alive when
+/// any of its cells is.
+fn downsample(grid: &Grid<CellState>, scale: usize) -> Vec<Vec<bool>> {
+    let scale = scale.max(1);
+    let mut blocks = vec![vec![false; grid.width.div_ceil(scale)]; grid.height.div_ceil(scale)];
+    for (idx, cell) in grid.cells.iter().enumerate() {
+        if cell.is_alive() {
+            blocks[idx / grid.width / scale][idx % grid.width / scale] = true;
+        }
+    }
+    blocks
+}
+
+/// Renders the whole of `grid` shrunk into the bottom right corner, highlighting the blocks of
+/// the `screen` columns and rows shown from `viewport` on, and returns the area it covers.
+fn render_minimap(
+    frame: &mut Frame<'_>,
+    grid: &Grid<CellState>,
+    viewport: Point,
+    screen: (usize, usize),
+    theme: &Theme,
+) -> Rect {
+    let scale = minimap_scale(grid.width, grid.height);
+    let on_screen = |block: usize, start: i32, length: usize| {
+        let start = start.max(0) as usize;
+        block * scale < start + length && (block + 1) * scale > start
+    };
+    let lines: Vec<Line> = downsample(grid, scale)
+        .into_iter()
+        .enumerate()
+        .map(|(y, row)| {
+            let shown_row = on_screen(y, viewport.y, screen.1);
+            let spans: Vec<Span> = row
+                .into_iter()
+                .enumerate()
+                .map(|(x, alive)| {
+                    let span = Span::raw(if alive { "█" } else { " " });
+                    if shown_row && on_screen(x, viewport.x, screen.0) {
+                        span.bg(theme.cell)
+                    } else {
+                        span
+                    }
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    let width = lines.first().map_or(0, |line| line.spans.len()) as u16 + 2;
+    let height = lines.len() as u16 + 2;
+    let screen = frame.area();
+    let area = Rect::new(
+        screen.right().saturating_sub(width),
+        screen.bottom().saturating_sub(height),
+        width,
+        height,
+    )
+    .clamp(screen);
+    let block = Block::bordered()
+        .border_style(theme.border)
+        .style(Style::default().fg(theme.text).bg(theme.background));
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+    area
+}
+
+/// Renders `text` in a box just below and right of `position`, moved back onto the screen when
+/// it would run off the edge.
+fn render_tooltip(frame: &mut Frame<'_>, position: Position, text: &str) {
+    let screen = frame.area();
+    let width = (text.chars().count() as u16 + 2).min(screen.width);
+    let height = 3.min(screen.height);
+    let area = Rect::new(
+        (position.x + 1).min(screen.right().saturating_sub(width)),
+        (position.y + 1).min(screen.bottom().saturating_sub(height)),
+        width,
+        height,
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(text).block(Block::bordered()), area);
+}
+
+/// Renders the keybindings in a box in the middle of the screen, over everything else.
+fn render_help(frame: &mut Frame<'_>, theme: &Theme) {
+    let lines = help_lines();
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0) as u16
+        + 4;
+    let height = lines.len() as u16 + 2;
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(theme.border)
+        .style(Style::default().fg(theme.text).bg(theme.background))
+        .title(" Keys ".bold())
+        .title_bottom(" ? or Esc to close ");
+    let text: Vec<Line> = lines.into_iter().map(Line::from).collect();
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(text).block(block.padding(Padding::horizontal(1))),
+        area,
+    );
+}
+
+fn render_links(frame: &mut Frame<'_>, links_area: Rect, focused: Option<usize>) {
+    let block = Block::bordered().title("Links".bold());
+    let inner = block.inner(links_area);
+    frame.render_widget(block, links_area);
+    // The icon and a space come before each label
+    let label_width = LINKS.iter().map(|link| link.label.len()).max().unwrap_or(0) as u16 + 3;
+    let rows = Layout::vertical(vec![Constraint::Length(1); LINKS.len()]).split(inner);
+    for (index, (link, row)) in LINKS.iter().zip(rows.iter()).enumerate() {
+        let [label_area, url_area] =
+            Layout::horizontal([Constraint::Length(label_width), Constraint::Fill(1)]).areas(*row);
+        frame.render_widget(
+            Line::from(vec![
+                Span::raw(format!("{} ", link.icon)).dim(),
+                Span::raw(link.label),
+            ]),
+            label_area,
+        );
+        let url = link.url;
+
+        // Ratzilla links to the text of the hyperlink itself, so a truncated URL would lead
+        // somewhere else. Show it shortened but not clickable when it doesn't fit the box.
+        if url.len() <= url_area.width as usize {
+            frame.render_widget(Hyperlink::new(url), url_area);
+        } else {
+            let visible = url_area.width.saturating_sub(1) as usize;
+            let shortened: String = url.chars().take(visible).chain(['…']).collect();
+            frame.render_widget(Span::raw(shortened).dim(), url_area);
+        }
+        if focused == Some(index) {
+            frame
+                .buffer_mut()
+                .set_style(*row, Style::default().reversed());
+        }
+    }
+}
+
+fn render_meetups(frame: &mut Frame<'_>, meetups_area: Rect, meetups: &[&Meetup]) {
+    let block = Block::bordered().title("Meetups".bold());
+    let inner = block.inner(meetups_area);
+    frame.render_widget(block, meetups_area);
+    if meetups.is_empty() {
+        frame.render_widget(Paragraph::new("Coming soon!"), inner);
+        return;
+    }
+    let lines = inner.height as usize;
+    // Keep the last line to tell how many meetups did not fit
+    let shown = if meetups.len() > lines {
+        lines.saturating_sub(1)
+    } else {
+        meetups.len()
+    };
+    let rows = Layout::vertical(vec![Constraint::Length(1); lines]).split(inner);
+    for (meetup, row) in meetups.iter().zip(rows.iter()).take(shown) {
+        let line = Line::from(vec![
+            Span::raw(meetup.date).bold(),
+            Span::raw(" "),
+            Span::raw(meetup.title),
+            Span::raw(format!(" @ {}", meetup.location)).dim(),
+        ]);
+        let url_width = meetup.url.len() as u16;
+        // Only link the meetup when the whole URL fits next to its description
+        if line.width() as u16 + 1 + url_width <= row.width {
+            let [text_area, url_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(url_width)])
+                    .areas(*row);
+            frame.render_widget(line, text_area);
+            frame.render_widget(Hyperlink::new(meetup.url), url_area);
+        } else {
+            frame.render_widget(line, *row);
+        }
+    }
+    if shown < meetups.len() {
+        let more = Line::from(format!("… and {} more", meetups.len() - shown)).dim();
+        frame.render_widget(more, rows[shown]);
+    }
+}
+
+fn render_description(frame: &mut Frame<'_>, description: String, description_area: Rect) {
+    frame.render_widget(
+        Paragraph::new(description)
+            .wrap(Wrap { trim: true })
+            .left_aligned()
+            .block(Block::bordered()),
+        description_area,
+    );
+}
+
+fn render_banner(frame: &mut Frame<'_>, banner: &str, banner_area: Rect) {
+    frame.render_widget(
+        Paragraph::new(banner).alignment(Alignment::Center),
+        banner_area,
+    );
+}
+
+/// The area of the background panel around the content `area` laid out with `constraints`,
+/// leaving room for its border, kept within `screen`. Content at the edge of the screen gets no
+/// margin on that side.
+fn background_area(area: Rect, constraints: &[Constraint], screen: Rect) -> Rect {
+    let height = constraints
+        .iter()
+        .map(|c| match *c {
+            Constraint::Min(v) | Constraint::Max(v) | Constraint::Length(v) => v,
+            _ => 0,
+        })
+        .fold(0u16, u16::saturating_add);
+    Rect::new(
+        area.x.saturating_sub(2),
+        area.y.saturating_sub(1),
+        area.width.saturating_add(4),
+        height.saturating_add(3),
+    )
+    .clamp(screen)
+}
+
+fn render_background(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    title: Option<String>,
+    constraints: &[Constraint],
+    theme: &Theme,
+) -> Rect {
+    let area = background_area(area, constraints, frame.area());
+    let mut block = Block::bordered()
+        .border_type(BorderType::Rounded)
+        .border_style(theme.border)
+        .style(Style::default().fg(theme.text).bg(theme.background))
+        .title_bottom("|built with Ratzilla|")
+        .title_alignment(Alignment::Right);
+    if let Some(title) = title {
+        block = block.title_top(Line::from(title).alignment(Alignment::Center).bold());
+    }
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratzilla::ratatui::style::Modifier;
+
+    #[test]
+    fn test_simulation_step_while_paused() {
+        let mut simulation = Simulation::Running;
+        assert!(simulation.advance());
+        simulation.step();
+        assert_eq!(simulation, Simulation::Running);
+
+        simulation.toggle_pause();
+        assert!(!simulation.advance());
+        simulation.step();
+        assert!(simulation.advance());
+        assert_eq!(simulation, Simulation::Paused);
+        assert!(!simulation.advance());
+    }
+
+    #[test]
+    fn test_app_counts_generations() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        app.generations_per_second = 4;
+        assert!(!app.tick(0.0));
+        assert!(app.tick(250.0));
+        app.simulation.toggle_pause();
+        assert!(!app.tick(500.0));
+        app.simulation.step();
+        assert!(app.tick(510.0));
+        assert!(!app.tick(750.0));
+        assert_eq!(app.generation, 2);
+    }
+
+    #[test]
+    fn test_app_speed_ignores_frame_rate() {
+        for frames_per_second in [32, 64, 128] {
+            let mut app = App::new(Grid::new_empty(3, 3));
+            app.generations_per_second = 16;
+            for frame in 0..=frames_per_second {
+                app.tick(f64::from(frame) * 1000.0 / f64::from(frames_per_second));
+            }
+            assert_eq!(app.generation, 16, "{frames_per_second} frames per second");
+        }
+    }
+
+    #[test]
+    fn test_app_caps_generations_per_frame() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        app.generations_per_second = 60;
+        app.tick(0.0);
+        app.tick(200.0);
+        assert_eq!(app.generation, u64::from(MAX_GENERATIONS_PER_FRAME));
+        assert_eq!(app.pending_ms, 0.0);
+        app.reset_clock();
+        app.tick(10_000.0);
+        assert_eq!(app.generation, u64::from(MAX_GENERATIONS_PER_FRAME));
+    }
+
+    #[test]
+    fn test_app_catches_up_on_a_single_frame_at_most() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        app.tick(0.0);
+        app.tick(60_000.0);
+        let due = MAX_FRAME_MS * f64::from(DEFAULT_GENERATIONS_PER_SECOND) / 1000.0;
+        assert_eq!(app.generation, due as u64);
+    }
+
+    #[test]
+    fn test_app_speed_is_clamped() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        for _ in 0..100 {
+            app.adjust_speed(1);
+        }
+        assert_eq!(app.generations_per_second, *GENERATIONS_PER_SECOND.end());
+        for _ in 0..100 {
+            app.adjust_speed(-1);
+        }
+        assert_eq!(app.generations_per_second, *GENERATIONS_PER_SECOND.start());
+    }
+
+    #[test]
+    fn test_age_color_gradient() {
+        let gradient = theme::DEFAULT.age_gradient;
+        let [green, teal, blue] = gradient;
+        assert_eq!(
+            age_color(0, gradient),
+            Color::Rgb(green.0, green.1, green.2)
+        );
+        assert_eq!(
+            age_color(AGE_GRADIENT_SPAN / 2, gradient),
+            Color::Rgb(teal.0, teal.1, teal.2)
+        );
+        assert_eq!(
+            age_color(AGE_GRADIENT_SPAN, gradient),
+            Color::Rgb(blue.0, blue.1, blue.2)
+        );
+        assert_eq!(
+            age_color(u16::MAX, gradient),
+            age_color(AGE_GRADIENT_SPAN, gradient)
+        );
+    }
+
+    #[test]
+    fn test_render_game_of_life() {
+        let mut grid = Grid::new_empty(3, 2);
+        grid.toggle_cell(Point::new(1, 0));
+        let backend = ratzilla::ratatui::backend::TestBackend::new(3, 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    CellWidth::Single,
+                    Point::new(0, 0),
+                    &[],
+                )
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..2)
+            .map(|y| (0..3).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, [" X ", "   "]);
+        assert_eq!(buffer[(1, 0)].fg, theme::DEFAULT.cell);
+
+        grid.set_age_tracking(true);
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    CellWidth::Single,
+                    Point::new(0, 0),
+                    &[],
+                )
+            })
+            .unwrap();
+        assert_eq!(
+            terminal.backend().buffer()[(1, 0)].fg,
+            age_color(0, theme::DEFAULT.age_gradient)
+        );
+    }
+
+    #[test]
+    fn test_downsample() {
+        let grid = Grid::from_plaintext("O....\n.....\n...OO").unwrap();
+        assert_eq!(
+            downsample(&grid, 2),
+            [[true, false, false], [false, true, true]]
+        );
+        assert_eq!(downsample(&grid, 5), [[true]]);
+        assert_eq!(downsample(&grid, 1)[2], [false, false, false, true, true]);
+        assert_eq!(minimap_scale(5, 3), 1);
+        assert_eq!(minimap_scale(200, 100), 13);
+    }
+
+    #[test]
+    fn test_minimap_recenters_view() {
+        let mut app = App::new(Grid::new_empty(1, 1));
+        app.min_grid_size = (200, 100);
+        app.fit(Rect::new(0, 0, 40, 20));
+        assert!(app.zoomed_in());
+        let backend = ratzilla::ratatui::backend::TestBackend::new(40, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                app.minimap =
+                    render_minimap(frame, &app.grid, app.viewport, app.screen, &theme::DEFAULT);
+            })
+            .unwrap();
+        // 16 by 8 blocks of 13 by 13 cells, the view covering the top left ones
+        assert_eq!(app.minimap, Rect::new(22, 10, 18, 10));
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(23, 11)].bg, theme::DEFAULT.cell);
+        assert_eq!(buffer[(27, 11)].bg, theme::DEFAULT.background);
+
+        app.click(Position::new(31, 15));
+        assert_eq!(app.viewport, Point::new(90, 48));
+        assert_eq!(app.grid.population(), 0);
+        app.click(Position::new(38, 18));
+        assert_eq!(app.viewport, Point::new(160, 80));
+    }
+
+    #[test]
+    fn test_double_width_cells() {
+        let mut app = App::new(Grid::new_empty(1, 1));
+        app.cell_width = CellWidth::Double;
+        app.fit(Rect::new(0, 0, 5, 2));
+        assert_eq!((app.grid.width, app.grid.height), (3, 2));
+        app.click(Position::new(3, 0));
+        app.click(Position::new(4, 1));
+        let backend = ratzilla::ratatui::backend::TestBackend::new(5, 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &app.grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    app.cell_width,
+                    app.viewport,
+                    &[],
+                )
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..2)
+            .map(|y| (0..5).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["  XX ", "    X"]);
+    }
+
+    #[test]
+    fn test_pan_clamps_to_grid() {
+        let mut app = App::new(Grid::new_empty(1, 1));
+        app.fit(Rect::new(0, 0, 10, 5));
+        app.pan(3, 3);
+        // The grid matches the screen, so there is nowhere to pan to
+        assert_eq!(app.viewport, Point::new(0, 0));
+
+        app.min_grid_size = (25, 8);
+        app.fit(Rect::new(0, 0, 10, 5));
+        assert_eq!((app.grid.width, app.grid.height), (25, 8));
+        let shift = |code| KeyEvent {
+            code,
+            ctrl: false,
+            alt: false,
+            shift: true,
+        };
+        app.handle_key(shift(KeyCode::Right));
+        app.handle_key(shift(KeyCode::Down));
+        assert_eq!(app.viewport, Point::new(PAN_STEP, 3));
+        app.pan(100, 0);
+        assert_eq!(app.viewport, Point::new(15, 3));
+        app.pan(-100, -100);
+        assert_eq!(app.viewport, Point::new(0, 0));
+
+        // Growing the screen pulls the view back onto the grid
+        app.pan(100, 100);
+        app.fit(Rect::new(0, 0, 20, 5));
+        assert_eq!(app.viewport, Point::new(5, 3));
+        assert_eq!(app.cell_at(Position::new(1, 1)), Some(Point::new(6, 4)));
+    }
+
+    #[test]
+    fn test_paste_large_pattern_pans() {
+        let mut app = App::new(Grid::new_empty(1, 1));
+        app.fit(Rect::new(0, 0, 4, 3));
+        let row = "O".repeat(9);
+        app.paste(&format!("{row}\n.........\n.........\n.........\n{row}"));
+        assert_eq!((app.grid.width, app.grid.height), (9, 5));
+        assert_eq!(app.grid.population(), 18);
+        assert_eq!(app.viewport, Point::new(2, 1));
+        app.fit(Rect::new(0, 0, 4, 3));
+        assert_eq!((app.grid.width, app.grid.height), (9, 5));
+
+        let backend = ratzilla::ratatui::backend::TestBackend::new(4, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &app.grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    app.cell_width,
+                    app.viewport,
+                    &[],
+                )
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..3)
+            .map(|y| (0..4).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["    ", "    ", "    "]);
+        app.pan(0, 1);
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &app.grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    app.cell_width,
+                    app.viewport,
+                    &[],
+                )
+            })
+            .unwrap();
+        assert_eq!(terminal.backend().buffer()[(0, 2)].symbol(), "X");
+    }
+
+    #[test]
+    fn test_mask_hides_cells_under_panels() {
+        let grid = Grid::from_plaintext("OOOO\nOOOO\nOOOO").unwrap();
+        let backend = ratzilla::ratatui::backend::TestBackend::new(4, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mask = [Rect::new(1, 1, 2, 2)];
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    CellWidth::Single,
+                    Point::new(0, 0),
+                    &mask,
+                )
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..3)
+            .map(|y| (0..4).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        assert_eq!(rows, ["XXXX", "X  X", "X  X"]);
+
+        let mut app = App::new(Grid::new_empty(1, 1));
+        let key = KeyEvent {
+            code: KeyCode::Char('k'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+        app.handle_key(key);
+        assert!(app.mask_panels);
+    }
+
+    #[test]
+    fn test_narrow_screens() {
+        assert_eq!(content_percentages(160, false), (80, 60));
+        assert_eq!(content_percentages(60, false), (80, 90));
+        assert_eq!(content_percentages(60, true), (30, 90));
+        assert_eq!(banner(120), BANNER);
+        assert_eq!(banner(40), COMPACT_BANNER);
+    }
+
+    #[test]
+    fn test_splash_is_in_page() {
+        let page = include_str!("../index.html");
+        assert!(page.contains(&format!(r#"<pre id="{SPLASH_ID}">"#)));
+        assert!(page.contains("loading…"));
+    }
+
+    #[test]
+    fn test_alive_glyphs() {
+        for glyph in ALIVE_GLYPHS {
+            assert_eq!(Span::raw(glyph.to_string()).width(), 1, "{glyph:?}");
+        }
+        // The top left corner is clear of the panels on a large screen
+        let mut app = App::new(Grid::from_plaintext("O.").unwrap());
+        let backend = ratzilla::ratatui::backend::TestBackend::new(120, 40);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut corner = |app: &mut App| {
+            terminal
+                .draw(|frame| {
+                    app.fit(frame.area());
+                    render_app(frame, app, &[], false);
+                })
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            [buffer[(0, 0)].symbol(), buffer[(1, 0)].symbol()].concat()
+        };
+        assert_eq!(corner(&mut app), "X ");
+        app.cycle_glyph();
+        app.cycle_glyph();
+        assert_eq!(app.glyphs().alive, '█');
+        assert_eq!(corner(&mut app), "█ ");
+        for _ in 2..ALIVE_GLYPHS.len() {
+            app.cycle_glyph();
+        }
+        assert_eq!(app.glyphs(), CELL_GLYPHS);
+    }
+
+    #[test]
+    fn test_description_width() {
+        assert_eq!(description_width(0), MIN_DESCRIPTION_WIDTH);
+        assert_eq!(description_width(14), MIN_DESCRIPTION_WIDTH);
+        assert_eq!(description_width(15), MIN_DESCRIPTION_WIDTH);
+        assert_eq!(description_width(100), 85);
+    }
+
+    #[test]
+    fn test_background_area_at_edges() {
+        let screen = Rect::new(0, 0, 80, 24);
+        let constraints = [Constraint::Length(3), Constraint::Length(4)];
+        let centered = background_area(Rect::new(10, 5, 20, 7), &constraints, screen);
+        assert_eq!(centered, Rect::new(8, 4, 24, 10));
+        let origin = background_area(Rect::new(0, 0, 20, 7), &constraints, screen);
+        assert_eq!(origin, Rect::new(0, 0, 24, 10));
+        let near_origin = background_area(Rect::new(1, 0, 20, 7), &constraints, screen);
+        assert_eq!((near_origin.x, near_origin.y), (0, 0));
+    }
+
+    #[test]
+    fn test_background_area_oversized() {
+        let screen = Rect::new(0, 0, 80, 24);
+        let constraints = [Constraint::Length(u16::MAX), Constraint::Length(u16::MAX)];
+        let area = background_area(Rect::new(0, 0, u16::MAX, 7), &constraints, screen);
+        assert_eq!(area, screen);
+        let tiny = Rect::new(0, 0, 3, 2);
+        assert_eq!(background_area(Rect::new(1, 1, 1, 1), &[], tiny), tiny);
+    }
+
+    #[test]
+    fn test_css_color() {
+        assert_eq!(css_color(Color::Rgb(16, 24, 255)), "#1018ff");
+        assert_eq!(css_color(Color::Reset), "#000000");
+    }
+
+    #[test]
+    fn test_live_rle_covers_live_cells() {
+        let mut grid = Grid::new_empty(20, 10);
+        assert_eq!(live_rle(&grid), None);
+        grid.insert(&patterns::glider(), Point::new(5, 4));
+        let copied = Grid::from_rle(&live_rle(&grid).unwrap()).unwrap();
+        assert_eq!((copied.width, copied.height), (3, 3));
+        assert_eq!(copied.cells, patterns::glider().cells);
+    }
+
+    #[test]
+    fn test_line() {
+        let line_between = |from: (i32, i32), to: (i32, i32)| {
+            line(Point::new(from.0, from.1), Point::new(to.0, to.1))
+                .into_iter()
+                .map(|p| (p.x, p.y))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(line_between((1, 1), (1, 1)), [(1, 1)]);
+        assert_eq!(
+            line_between((0, 0), (3, 0)),
+            [(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+        assert_eq!(line_between((2, 2), (0, 0)), [(2, 2), (1, 1), (0, 0)]);
+        assert_eq!(
+            line_between((0, 0), (4, 2)),
+            [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]
+        );
+    }
+
+    #[test]
+    fn test_mouse_paints_strokes_while_paused() {
+        let mut app = App::new(Grid::new_empty(6, 3));
+        app.mouse(CellMouse::Down {
+            position: Position::new(0, 0),
+            erase: false,
+            select: false,
+        });
+        app.mouse(CellMouse::Move(Position::new(4, 0)));
+        assert_eq!(app.grid.population(), 0, "running");
+
+        app.simulation = Simulation::Paused;
+        app.mouse(CellMouse::Down {
+            position: Position::new(0, 0),
+            erase: false,
+            select: false,
+        });
+        app.mouse(CellMouse::Move(Position::new(4, 0)));
+        app.mouse(CellMouse::Move(Position::new(4, 2)));
+        app.mouse(CellMouse::Up);
+        app.click(Position::new(4, 2));
+        assert_eq!(app.grid.population(), 7);
+        app.mouse(CellMouse::Move(Position::new(5, 2)));
+        assert_eq!(app.grid.population(), 7, "released");
+
+        app.mouse(CellMouse::Down {
+            position: Position::new(0, 0),
+            erase: true,
+            select: false,
+        });
+        app.mouse(CellMouse::Move(Position::new(2, 0)));
+        app.mouse(CellMouse::Up);
+        app.click(Position::new(2, 0));
+        assert_eq!(app.grid.population(), 4);
+        app.click(Position::new(0, 0));
+        assert_eq!(app.grid.population(), 5, "plain click");
+    }
+
+    #[test]
+    fn test_keybindings_are_reachable() {
+        // Every binding is the first to match its own key, so none is shadowed by another
+        for binding in KEYBINDINGS {
+            let event = KeyEvent {
+                code: binding.code.clone(),
+                ctrl: false,
+                alt: false,
+                shift: binding.shift,
+            };
+            let first = KEYBINDINGS.iter().find(|other| other.matches(&event));
+            assert_eq!(first.map(|first| first.action), Some(binding.action));
+        }
+        let lines = help_lines();
+        assert!(lines.contains(&"Space         Pause or resume".to_string()));
+        assert!(lines.contains(&"n, →          Step one generation".to_string()));
+        assert!(lines.contains(&"Shift+Tab, ↑  Focus the previous link".to_string()));
+        assert!(lines.contains(&"w             Toggle random rain".to_string()));
+    }
+
+    #[test]
+    fn test_help_overlay() {
+        let mut app = App::new(Grid::new_empty(1, 1));
+        let press = |code| KeyEvent {
+            code,
+            ctrl: false,
+            alt: false,
+            shift: true,
+        };
+        app.handle_key(press(KeyCode::Char('?')));
+        assert!(app.help);
+        app.focused_link = Some(0);
+        app.handle_key(press(KeyCode::Esc));
+        assert!(!app.help);
+        assert_eq!(app.focused_link, Some(0));
+        app.handle_key(press(KeyCode::Esc));
+        assert_eq!(app.focused_link, None);
+        app.handle_key(press(KeyCode::Char('?')));
+        app.handle_key(press(KeyCode::Char('?')));
+        assert!(!app.help);
+
+        app.help = true;
+        let backend = ratzilla::ratatui::backend::TestBackend::new(80, 40);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                app.fit(frame.area());
+                render_app(frame, &mut app, &[], false);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Keys"));
+        assert!(screen.contains("Toggle this help"));
+    }
+
+    #[test]
+    fn test_hover_tooltip() {
+        let mut app = App::new(Grid::from_plaintext("OO.\n...\n.O.").unwrap());
+        app.mouse(CellMouse::Move(Position::new(1, 1)));
+        assert_eq!(app.hover_text(), None);
+
+        app.handle_key(KeyEvent {
+            code: KeyCode::Char('i'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        assert_eq!(
+            app.hover_text().as_deref(),
+            Some("(1, 1) dead, 3 alive neighbours")
+        );
+        app.mouse(CellMouse::Move(Position::new(0, 0)));
+        assert_eq!(
+            app.hover_text().as_deref(),
+            Some("(0, 0) alive, 1 alive neighbours")
+        );
+
+        // Nothing is described over the panels, or off the grid
+        app.panels = Rect::new(0, 0, 1, 1);
+        assert_eq!(app.hover_text(), None);
+        app.mouse(CellMouse::Move(Position::new(7, 1)));
+        assert_eq!(app.hover_text(), None);
+
+        let backend = ratzilla::ratatui::backend::TestBackend::new(12, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| render_tooltip(frame, Position::new(11, 3), "(0, 0)"))
+            .unwrap();
+        terminal.backend().assert_buffer_lines([
+            "            ",
+            "    ┌──────┐",
+            "    │(0, 0)│",
+            "    └──────┘",
+        ]);
+    }
+
+    #[test]
+    fn test_mouse_fills_selection() {
+        let mut app = App::new(Grid::new_empty(6, 4));
+        app.simulation = Simulation::Paused;
+        let select = |app: &mut App, from: (u16, u16), to: (u16, u16), erase| {
+            app.mouse(CellMouse::Down {
+                position: Position::new(from.0, from.1),
+                erase,
+                select: true,
+            });
+            app.mouse(CellMouse::Move(Position::new(to.0, to.1)));
+            let before_release = app.grid.population();
+            app.mouse(CellMouse::Up);
+            app.click(Position::new(to.0, to.1));
+            before_release
+        };
+        assert_eq!(select(&mut app, (4, 3), (1, 1), false), 0);
+        assert_eq!(app.grid.to_string(), "      \n XXXX \n XXXX \n XXXX \n");
+        app.grid.clear();
+        select(&mut app, (0, 0), (5, 3), false);
+        select(&mut app, (1, 1), (4, 2), true);
+        assert_eq!(app.grid.population(), 16, "cleared the middle");
+    }
+
+    #[test]
+    fn test_paste_pattern() {
+        let mut app = App::new(Grid::new_random(9, 9));
+        app.paste("x = 3, y = 3, rule = B36/S23\nbo$2bo$3o!");
+        assert_eq!(app.simulation, Simulation::Paused);
+        assert_eq!(app.grid.population(), 5);
+        assert_eq!(
+            app.grid.live_bounds(),
+            Some((Point::new(3, 3), Point::new(5, 5)))
+        );
+        assert_eq!(
+            app.grid.ruleset().map(|r| r.to_string()),
+            Some("B36/S23".to_string())
+        );
+        assert!(app.status().ends_with("|Pasted!|"));
+
+        app.paste("!Name: Blinker\nOOO");
+        assert_eq!(
+            app.grid.live_bounds(),
+            Some((Point::new(3, 4), Point::new(5, 4)))
+        );
+
+        assert_eq!(app.grid.ruleset(), Some(Ruleset::conway()));
+
+        let before = app.grid.cells.clone();
+        app.paste("not a pattern");
+        assert_eq!(app.grid.cells, before);
+        assert!(app
+            .status()
+            .ends_with("|Could not read the pasted pattern|"));
+
+        // A header asking for more cells than a grid holds is rejected before allocating them
+        app.toast.set(None);
+        app.paste("x = 100000, y = 100000\no!");
+        assert_eq!(app.grid.cells, before);
+        assert_eq!((app.grid.width, app.grid.height), (9, 9));
+        assert!(app
+            .status()
+            .ends_with("|Could not read the pasted pattern|"));
+        assert!(decode_board(&format!("{BOARD_FORMAT}x = 100000, y = 100000\no!"), 9, 9).is_none());
+    }
+
+    #[test]
+    fn test_toast_expires() {
+        let app = App::new(Grid::new_empty(3, 3));
+        app.toast.set(Some(Toast::new("Copied!")));
+        assert!(app.status().ends_with("|Copied!|"));
+        for _ in 0..TOAST_FRAMES {
+            app.expire_toast();
+        }
+        assert!(app.status().ends_with("|Copied!|"));
+        app.expire_toast();
+        assert!(!app.status().contains("Copied!"));
+    }
+
+    #[test]
+    fn test_board_round_trip() {
+        let grid = Grid::new_random(12, 7);
+        let restored = decode_board(&encode_board(&grid), 12, 7).unwrap();
+        assert_eq!(restored.cells, grid.cells);
+    }
+
+    #[test]
+    fn test_decode_board_resized() {
+        let mut grid = Grid::new_empty(4, 4);
+        grid.toggle_cell(Point::new(1, 1));
+        grid.toggle_cell(Point::new(3, 3));
+        let restored = decode_board(&encode_board(&grid), 3, 5).unwrap();
+        assert_eq!((restored.width, restored.height), (3, 5));
+        assert_eq!(restored.population(), 1);
+    }
+
+    #[test]
+    fn test_decode_board_rejects_old_or_corrupt_saves() {
+        let grid = Grid::new_random(4, 4);
+        assert!(decode_board(&grid.to_rle(), 4, 4).is_none());
+        assert!(decode_board("v0\nx = 4, y = 4\n!", 4, 4).is_none());
+        assert!(decode_board("v1\nnot a board", 4, 4).is_none());
+    }
+
+    #[test]
+    fn test_app_reseeds_settled_grid() {
+        let mut app = App::new(Grid::new_empty(20, 20));
+        for _ in 0..RESEED_AFTER_SETTLED - 1 {
+            app.advance_generation();
+        }
+        assert_eq!(app.grid.population(), 0);
+        app.advance_generation();
+        assert_eq!(app.generation, 0);
+        assert!(app.grid.population() > 0);
+    }
+
+    #[test]
+    fn test_ant_walks_its_own_board() {
+        let mut app = App::new(Grid::new_empty(9, 9));
+        app.ant = Some(LangtonAnt::new(9, 9));
+        app.advance_generation();
+        assert_eq!(app.generation, 1);
+        assert_eq!(app.board().population(), 1);
+        assert_eq!(app.grid.population(), 0);
+        assert_eq!(app.cell_at(Position::new(4, 4)), None);
+
+        let backend = ratzilla::ratatui::backend::TestBackend::new(9, 9);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let ant = app.ant.as_ref().unwrap();
+        terminal
+            .draw(|frame| render_ant(ant, frame, &theme::DEFAULT, CellWidth::Single, app.viewport))
+            .unwrap();
+        let target = &terminal.backend().buffer()[(5, 4)];
+        assert_eq!((target.symbol(), target.fg), ("▶", theme::DEFAULT.border));
+
+        app.reseed();
+        assert_eq!(app.board().population(), 0);
+    }
+
+    #[test]
+    fn test_render_app_on_native_backend() {
+        for (width, mobile) in [(120, false), (60, true)] {
+            let backend = ratzilla::ratatui::backend::TestBackend::new(width, 40);
+            let mut terminal = Terminal::new(backend).unwrap();
+            let mut app = App::new(Grid::new_random(width.into(), 40));
+            terminal
+                .draw(|frame| {
+                    app.fit(frame.area());
+                    render_app(frame, &mut app, &[], mobile);
+                })
+                .unwrap();
+            assert!(app.panels.width > 0 && app.panels.height > 0);
+            let buffer = terminal.backend().buffer();
+            let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+            assert!(text.contains("GitHub"));
+            assert!(text.contains("|generation 0|"));
+        }
+    }
+
+    #[test]
+    fn test_render_app_on_empty_screen() {
+        for (width, height) in [(0, 0), (0, 30), (80, 0)] {
+            let backend = ratzilla::ratatui::backend::TestBackend::new(width, height);
+            let mut terminal = Terminal::new(backend).unwrap();
+            let mut app = App::new(Grid::new_random(width.into(), height.into()));
+            app.hud.visible = true;
+            app.inspect = true;
+            app.help = true;
+            app.hover = Some(Position::new(0, 0));
+            app.reseed();
+            for _ in 0..2 {
+                terminal
+                    .draw(|frame| {
+                        app.fit(frame.area());
+                        app.advance_generation();
+                        render_app(frame, &mut app, &[], false);
+                    })
+                    .unwrap();
+            }
+            assert!(app.grid.cells.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_reseed_cross_fades() {
+        let mut app = App::new(Grid::from_plaintext("OO\n..").unwrap());
+        app.reseed();
+        let fade = app.cross_fade.clone().unwrap();
+        assert_eq!(fade.old, Grid::from_plaintext("OO\n..").unwrap().cells);
+        assert_eq!(fade.progress(), 0.0);
+
+        // The old cells show in full and the new ones not at all when the fade starts
+        app.grid.clear();
+        app.grid.set_cell(Point::new(0, 1), CellState::Alive('X'));
+        let backend = ratzilla::ratatui::backend::TestBackend::new(2, 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                let (theme, origin) = (&theme::DEFAULT, Point::new(0, 0));
+                render_game_of_life(
+                    &app.grid,
+                    frame,
+                    theme,
+                    CELL_GLYPHS,
+                    CellWidth::Single,
+                    origin,
+                    &[],
+                );
+                render_cross_fade(
+                    &app.grid,
+                    &fade,
+                    frame,
+                    theme,
+                    CellWidth::Single,
+                    origin,
+                    &[],
+                );
+            })
+            .unwrap();
+        app.cross_fade = Some(fade);
+        let buffer = terminal.backend().buffer();
+        let (red, green, blue) = theme::DEFAULT.fade;
+        assert_eq!(buffer[(0, 0)].symbol(), CELL_GLYPHS.alive.to_string());
+        assert_eq!(buffer[(0, 0)].fg, theme::DEFAULT.cell);
+        assert_eq!(buffer[(0, 1)].fg, Color::Rgb(red, green, blue));
+
+        // The fade always ends, after a fixed number of frames
+        for _ in 0..=CROSS_FADE_FRAMES {
+            assert!(app.cross_fade.is_some());
+            app.advance_cross_fade();
+        }
+        assert_eq!(app.cross_fade, None);
+
+        app.motion = MotionMode::Reduced;
+        app.reseed();
+        assert_eq!(app.cross_fade, None);
+        app.motion = MotionMode::Full;
+        app.reseed();
+        app.clear();
+        assert_eq!(app.cross_fade, None);
+    }
+
+    #[test]
+    fn test_clusters_get_their_own_colors() {
+        let mut app = App::new(Grid::from_plaintext("O...\n...O\n..O.").unwrap());
+        app.handle_key(KeyEvent {
+            code: KeyCode::Char('o'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        assert!(app.clusters);
+        let backend = ratzilla::ratatui::backend::TestBackend::new(4, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                render_clusters(&app.grid, frame, CellWidth::Single, Point::new(0, 0), &[])
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let color = |(red, green, blue)| Color::Rgb(red, green, blue);
+        assert_eq!(buffer[(0, 0)].fg, color(CLUSTER_COLORS[0]));
+        assert_eq!(buffer[(3, 1)].fg, color(CLUSTER_COLORS[1]));
+        assert_eq!(buffer[(2, 2)].fg, color(CLUSTER_COLORS[1]));
+        assert_eq!(buffer[(1, 0)].fg, Color::Reset);
+    }
+
+    #[test]
+    fn test_spaceships_highlighted() {
+        let mut app = App::new(
+            Grid::from_plaintext(".O.....\n..O....\nOOO....\n.......\n.....OO\n.....OO").unwrap(),
+        );
+        app.handle_key(KeyEvent {
+            code: KeyCode::Char('v'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        assert!(app.spaceships);
+        let backend = ratzilla::ratatui::backend::TestBackend::new(7, 6);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                let origin = Point::new(0, 0);
+                render_spaceships(app.find_spaceships(), frame, CellWidth::Single, origin, &[]);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(1, 0)].fg, SPACESHIP_COLOR);
+        assert_eq!(buffer[(2, 2)].fg, SPACESHIP_COLOR);
+        assert_ne!(buffer[(6, 5)].fg, SPACESHIP_COLOR);
+        assert_eq!(app.find_spaceships().len(), 1);
+
+        // Found again only once the board moved on
+        app.found_spaceships.clear();
+        assert!(app.find_spaceships().is_empty());
+        app.advance_generation();
+        assert_eq!(app.find_spaceships().len(), 1);
+
+        // A pattern pasted over another of the same population at generation 0 is searched anew
+        let mut app = App::new(Grid::new_empty(20, 20));
+        app.paste(".O.\n..O\nOOO");
+        assert_eq!(app.find_spaceships().len(), 1);
+        app.paste("OO.\nO.O\n.O.");
+        assert_eq!((app.generation, app.grid.population()), (0, 5));
+        assert!(app.find_spaceships().is_empty());
+    }
+
+    #[test]
+    fn test_shimmer_shades_odd_births() {
+        let mut app = App::new(Grid::from_plaintext(".O.\n.O.\n.O.").unwrap());
+        app.handle_key(KeyEvent {
+            code: KeyCode::Char('e'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        app.grid.update_states();
+        let backend = ratzilla::ratatui::backend::TestBackend::new(3, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                let origin = Point::new(0, 0);
+                render_game_of_life(
+                    &app.grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    CellWidth::Single,
+                    origin,
+                    &[],
+                );
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let (red, green, blue) = theme::DEFAULT.fade;
+        let shaded = mix_color(
+            theme::DEFAULT.cell,
+            Color::Rgb(red, green, blue),
+            SHIMMER_SHADE,
+        );
+        assert_eq!(buffer[(0, 1)].fg, shaded);
+        assert_eq!(buffer[(1, 1)].fg, theme::DEFAULT.cell);
+        assert_ne!(shaded, theme::DEFAULT.cell);
+    }
+
+    #[test]
+    fn test_mix_color() {
+        let (from, to) = (Color::Rgb(0, 100, 200), Color::Rgb(100, 100, 0));
+        assert_eq!(mix_color(from, to, 0.0), from);
+        assert_eq!(mix_color(from, to, 0.5), Color::Rgb(50, 100, 100));
+        assert_eq!(mix_color(from, to, 1.0), to);
+        assert_eq!(mix_color(Color::Reset, to, 0.5), to);
+    }
+
+    #[test]
+    fn test_render_links_stay_inside_box() {
+        let backend = ratzilla::ratatui::backend::TestBackend::new(30, LINKS.len() as u16 + 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| render_links(frame, frame.area(), None))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        for y in 1..=LINKS.len() as u16 {
+            assert_eq!(buffer[(29, y)].symbol(), "│");
+        }
+        let first_row: String = (1..29).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert_eq!(first_row, "$ GitHub  https://github.co…");
+    }
+
+    #[test]
+    fn test_link_focus_wraps_around() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        let tab = |shift| KeyEvent {
+            code: KeyCode::Tab,
+            ctrl: false,
+            alt: false,
+            shift,
+        };
+        app.handle_key(tab(true));
+        assert_eq!(app.focused_link, Some(LINKS.len() - 1));
+        app.handle_key(tab(false));
+        assert_eq!(app.focused_link, Some(0));
+        app.move_link_focus(-1);
+        assert_eq!(app.focused_link, Some(LINKS.len() - 1));
+        app.move_link_focus(1);
+        assert_eq!(app.focused_link, Some(0));
+    }
+
+    #[test]
+    fn test_render_links_highlights_focus() {
+        let backend = ratzilla::ratatui::backend::TestBackend::new(60, LINKS.len() as u16 + 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| render_links(frame, frame.area(), Some(1)))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let reversed = |y| buffer[(1, y)].modifier.contains(Modifier::REVERSED);
+        assert!(!reversed(1));
+        assert!(reversed(2));
+    }
+
+    #[test]
+    fn test_reduced_motion_slows_down() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        app.motion = MotionMode::Reduced;
+        for frame in 0..4 {
+            app.tick(f64::from(frame) * 250.0);
+        }
+        assert_eq!(app.generation, 0);
+        app.tick(1000.0);
+        assert_eq!(app.generation, 1);
+        assert!(app.status().ends_with("reduced motion|"));
+    }
+
+    #[test]
+    fn test_rain_toggles() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        let key = KeyEvent {
+            code: KeyCode::Char('w'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+        app.handle_key(key.clone());
+        assert!(app.rain);
+        assert!(app.status().ends_with("rain|"));
+        app.handle_key(key);
+        assert!(!app.status().contains("rain"));
+    }
+
+    #[test]
+    fn test_glider_rain_spawns_gliders() {
+        let mut grid = Grid::new_empty(5, 8);
+        let mut rain = GliderRain::new(10, 1.0, GLIDER_RAIN_SEED);
+        assert_eq!(rain.spawn(&mut grid, 5), 0);
+        assert_eq!(rain.spawn(&mut grid, 10), 1);
+        let rightwards = patterns::glider();
+        let mut leftwards = patterns::glider();
+        leftwards.flip_horizontal();
+        let spawned = grid.crop(Point::new(1, 0), 3, 3);
+        let heading = if spawned.cells == rightwards.cells {
+            1
+        } else {
+            assert_eq!(spawned.cells, leftwards.cells);
+            -1
+        };
+        // The slot is taken until the glider has moved on
+        assert_eq!(rain.spawn(&mut grid, 20), 0);
+        for _ in 0..4 {
+            grid.update_states();
+        }
+        assert_eq!(
+            grid.live_bounds(),
+            Some((Point::new(1 + heading, 1), Point::new(3 + heading, 3)))
+        );
+
+        let mut too_short = Grid::new_empty(20, 2);
+        assert_eq!(rain.spawn(&mut too_short, 0), 0);
+        let mut wide = Grid::new_empty(20, 8);
+        assert_eq!(rain.spawn(&mut wide, 0), 4);
+    }
+
+    #[test]
+    fn test_glider_rain_does_not_reseed() {
+        let mut app = App::new(Grid::new_empty(10, 10));
+        app.glider_rain = Some(GliderRain::new(100, 0.0, GLIDER_RAIN_SEED));
+        for _ in 0..RESEED_AFTER_SETTLED * 2 {
+            app.advance_generation();
+        }
+        assert_eq!(app.grid.population(), 0);
+    }
+
+    #[test]
+    fn test_rulesets_cycle() {
+        let mut app = App::new(Grid::new_random(20, 10));
+        let cells = app.grid.cells.clone();
+        assert_eq!(app.rule_name(), "Conway");
+        let key = KeyEvent {
+            code: KeyCode::Char('u'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+        for named in RULESETS.iter().skip(1) {
+            app.handle_key(key.clone());
+            assert_eq!(app.rule_name(), named.name);
+            assert_eq!(app.toast.get().map(|toast| toast.text), Some(named.name));
+        }
+        // Switching rules leaves the cells alone
+        assert_eq!(app.grid.cells, cells);
+        assert_eq!(
+            app.grid.population(),
+            cells.iter().filter(|c| c.is_alive()).count()
+        );
+        app.handle_key(key);
+        assert_eq!(app.grid.ruleset(), Some(Ruleset::conway()));
+
+        app.grid.set_ruleset(Ruleset::parse("B1/S1").unwrap());
+        assert_eq!(app.rule_name(), "B1/S1");
+
+        // Cycling carries on from the rule of a pasted pattern
+        app.paste("x = 3, y = 1, rule = B36/S23\n3o!");
+        assert_eq!(app.rule_name(), "HighLife");
+        app.handle_key(KeyEvent {
+            code: KeyCode::Char('u'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        assert_eq!(app.rule_name(), "Day & Night");
+        assert_eq!(app.toast.get().map(|toast| toast.text), Some("Day & Night"));
+        app.paste("x = 3, y = 1, rule = B1/S1\n3o!");
+        app.cycle_ruleset();
+        assert_eq!(app.rule_name(), "Conway");
+    }
+
+    #[test]
+    fn test_heatmap() {
+        assert_eq!(heat_color(0.0, 0.9), None);
+        assert_eq!(heat_color(0.1, 0.9), None);
+        let [red, _, yellow] = HEAT_GRADIENT;
+        assert_eq!(
+            heat_color(10.0, 0.9),
+            Some(Color::Rgb(yellow.0, yellow.1, yellow.2))
+        );
+        assert_eq!(heat_color(100.0, 0.9), heat_color(10.0, 0.9));
+        let Some(Color::Rgb(warm, _, _)) = heat_color(1.0, 0.9) else {
+            panic!("a cell alive once is warm");
+        };
+        assert!(warm > red.0 && warm < yellow.0);
+
+        let mut app = App::new(Grid::from_plaintext("OO.\nOO.").unwrap());
+        let key = KeyEvent {
+            code: KeyCode::Char('h'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        };
+        app.handle_key(key.clone());
+        app.advance_generation();
+        let backend = ratzilla::ratatui::backend::TestBackend::new(3, 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| {
+                render_game_of_life(
+                    &app.grid,
+                    frame,
+                    &theme::DEFAULT,
+                    CELL_GLYPHS,
+                    app.cell_width,
+                    app.viewport,
+                    &[],
+                )
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(
+            buffer[(0, 0)].bg,
+            heat_color(1.0, Grid::DEFAULT_HEAT_DECAY).unwrap()
+        );
+        assert_eq!(buffer[(2, 0)].bg, theme::DEFAULT.page);
+        app.handle_key(key);
+        assert_eq!(app.grid.heat(), None);
+    }
+
+    #[test]
+    fn test_chosen_theme_ignores_color_scheme() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        app.follow_color_scheme(true);
+        assert_eq!(app.theme(), &theme::LIGHT);
+        app.follow_color_scheme(false);
+        assert_eq!(app.theme(), &theme::DEFAULT);
+        app.handle_key(KeyEvent {
+            code: KeyCode::Char('l'),
+            ctrl: false,
+            alt: false,
+            shift: false,
+        });
+        app.follow_color_scheme(false);
+        assert_eq!(app.theme(), &theme::LIGHT);
+    }
+
+    #[test]
+    fn test_dying_cells_fade_out() {
+        let dark = &theme::DEFAULT;
+        let dying = |level, theme| cell_color(&CellState::Dying(level), None, 3, theme);
+        assert_eq!(dying(2, dark), Color::Rgb(50, 50, 50));
+        assert_eq!(dying(0, &theme::LIGHT), Color::Rgb(241, 245, 249));
+    }
+
+    #[test]
+    fn test_second_color_cells_use_theme() {
+        let cell = CellState::AliveB('X');
+        assert_eq!(
+            cell_color(&cell, Some(40), 0, &theme::DEFAULT),
+            theme::DEFAULT.cell_b
+        );
+    }
+
+    #[test]
+    fn test_status_shows_generation_and_population() {
+        let mut app = App::new(Grid::new_empty(3, 3));
+        app.grid.toggle_cell(Point::new(1, 1));
+        app.grid.toggle_cell(Point::new(2, 1));
+        assert!(app.status().starts_with("|generation 0|population 2|"));
+        app.advance_generation();
+        assert!(app.status().starts_with("|generation 1|population 0|"));
+    }
+
+    #[test]
+    fn test_app_steps_back() {
+        let mut app = App::new(Grid::new_random(6, 6));
+        app.grid.set_history_capacity(HISTORY_LENGTH);
+        let start = app.grid.cells.clone();
+        app.advance_generation();
+        app.advance_generation();
+        app.step_back();
+        app.step_back();
+        assert_eq!(app.simulation, Simulation::Paused);
+        assert_eq!(app.generation, 0);
+        assert_eq!(app.grid.cells, start);
+        app.step_back();
+        assert_eq!(app.generation, 0);
+    }
+
+    #[test]
+    fn test_hud_measures_frame_rate() {
+        let mut hud = Hud::default();
+        hud.record_frame(0.0);
+        assert_eq!(hud.fps, 0.0);
+        hud.record_frame(20.0);
+        assert_eq!(hud.fps, 50.0);
+        hud.record_frame(30.0);
+        assert!(hud.fps > 50.0 && hud.fps < 100.0);
+        assert!(hud.text(7, "Conway").ends_with("cells 7\nrule Conway"));
+    }
+
+    fn meetup(title: &'static str, date: &'static str) -> Meetup {
+        Meetup {
+            title,
+            date,
+            location: "Online",
+            url: "https://terminalcollective.org",
+        }
+    }
+
+    #[test]
+    fn test_upcoming_meetups_skips_past_ones() {
+        let meetups = [
+            meetup("Past", "2024-12-31"),
+            meetup("Today", "2025-01-01"),
+            meetup("Later", "2025-02-01"),
+        ];
+        let titles: Vec<&str> = upcoming_meetups(&meetups, "2025-01-01")
+            .iter()
+            .map(|meetup| meetup.title)
+            .collect();
+        assert_eq!(titles, ["Today", "Later"]);
+    }
+
+    #[test]
+    fn test_render_meetups_truncates() {
+        let meetups: Vec<Meetup> = (1..=5)
+            .map(|_| meetup("Hack night", "2025-01-01"))
+            .collect();
+        let meetups: Vec<&Meetup> = meetups.iter().collect();
+        let height = meetups_height(meetups.len());
+        assert_eq!(height, MAX_MEETUP_LINES as u16 + 2);
+        let backend = ratzilla::ratatui::backend::TestBackend::new(40, height);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| render_meetups(frame, frame.area(), &meetups))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| -> String { (1..39).map(|x| buffer[(x, y)].symbol()).collect() };
+        assert!(row(1).starts_with("2025-01-01 Hack night @ Online"));
+        assert!(row(3).starts_with("… and 3 more"));
+    }
+
+    #[test]
+    fn test_links_point_to_their_platform() {
+        for link in LINKS {
+            let domain = match link.label {
+                "GitHub" => "github.com",
+                "Discord" => "discord.gg",
+                "YouTube" => "www.youtube.com",
+                "RSS" => "terminalcollective.org",
+                label => panic!("unknown platform {label}"),
+            };
+            let host = link.url.trim_start_matches("https://").split('/').next();
+            assert_eq!(host, Some(domain), "{}", link.label);
+        }
+    }
 }
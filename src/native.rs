@@ -0,0 +1,224 @@
+//! Runs the site in a real terminal with crossterm rather than in the browser, for development
+//! and demos: `cargo run --features native`. `q` or `Ctrl+C` quits.
+
+use std::io;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::event::{KeyCode as CrosstermKeyCode, KeyEvent as CrosstermKeyEvent};
+use ratatui::{DefaultTerminal, Frame};
+use ratzilla::event::{KeyCode, KeyEvent};
+
+use crate::{
+    prepare_background, render_app, upcoming_meetups, Action, App, Meetup, KEYBINDINGS, MEETUPS,
+    RESEED_DENSITY,
+};
+use game_of_life::{Grid, Symmetry};
+
+/// How long the event loop waits for a key before drawing the next frame, about 60 frames a
+/// second like the browser.
+const FRAME_TIME: Duration = Duration::from_millis(16);
+
+/// Sets up the terminal, runs the site in it until it is quit and restores the terminal.
+pub fn run() -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = run_app(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+fn run_app(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let size = terminal.size()?;
+    let mut app = new_app(size.width.into(), size.height.into());
+    let today = today();
+    let meetups = upcoming_meetups(MEETUPS, &today);
+    let start = Instant::now();
+    loop {
+        let now = start.elapsed().as_secs_f64() * 1000.0;
+        terminal.draw(|frame| draw(frame, &mut app, &meetups, now))?;
+        if !event::poll(FRAME_TIME)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if is_quit(&key) {
+            return Ok(());
+        }
+        if let Some(key) = key_event(key) {
+            handle_key(&mut app, key);
+        }
+    }
+}
+
+/// The app with a random symmetric board of `width` by `height` cells, like the browser shows
+/// on a first visit.
+fn new_app(width: usize, height: usize) -> App {
+    let mut grid = Grid::new_random_symmetric(width, height, Symmetry::Both, RESEED_DENSITY);
+    prepare_background(&mut grid);
+    App::new(grid)
+}
+
+/// Draws a frame of the site at `now`, in milliseconds since the site started, computing the
+/// generations due like the browser does before every frame.
+fn draw(frame: &mut Frame<'_>, app: &mut App, meetups: &[&Meetup], now: f64) {
+    app.fit(frame.area());
+    app.expire_toast();
+    app.advance_cross_fade();
+    app.hud.record_frame(now);
+    let update = Instant::now();
+    if app.tick(now) {
+        app.hud.update_ms = update.elapsed().as_secs_f64() * 1000.0;
+    }
+    render_app(frame, app, meetups, false);
+}
+
+/// Performs the action bound to `event`, leaving out the ones which need a browser.
+fn handle_key(app: &mut App, event: KeyEvent) {
+    let Some(binding) = KEYBINDINGS.iter().find(|binding| binding.matches(&event)) else {
+        return;
+    };
+    match binding.action {
+        // Downloads, the clipboard and links are all browser APIs
+        Action::ExportPng | Action::CopyRle | Action::OpenLink => {}
+        // Without saving the glyph to localStorage
+        Action::CycleGlyph => app.cycle_glyph(),
+        action => app.perform(action),
+    }
+}
+
+fn is_quit(key: &CrosstermKeyEvent) -> bool {
+    match key.code {
+        CrosstermKeyCode::Char('q') => true,
+        CrosstermKeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false,
+    }
+}
+
+/// The ratzilla key event for a crossterm one, so the keybindings of the browser apply, `None`
+/// for keys the browser has no code for.
+fn key_event(key: CrosstermKeyEvent) -> Option<KeyEvent> {
+    let mut shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    let code = match key.code {
+        CrosstermKeyCode::Char(c) => KeyCode::Char(c),
+        CrosstermKeyCode::F(n) => KeyCode::F(n),
+        CrosstermKeyCode::Backspace => KeyCode::Backspace,
+        CrosstermKeyCode::Enter => KeyCode::Enter,
+        CrosstermKeyCode::Left => KeyCode::Left,
+        CrosstermKeyCode::Right => KeyCode::Right,
+        CrosstermKeyCode::Up => KeyCode::Up,
+        CrosstermKeyCode::Down => KeyCode::Down,
+        CrosstermKeyCode::Tab => KeyCode::Tab,
+        // Terminals report Shift+Tab as a key of its own
+        CrosstermKeyCode::BackTab => {
+            shift = true;
+            KeyCode::Tab
+        }
+        CrosstermKeyCode::Delete => KeyCode::Delete,
+        CrosstermKeyCode::Home => KeyCode::Home,
+        CrosstermKeyCode::End => KeyCode::End,
+        CrosstermKeyCode::PageUp => KeyCode::PageUp,
+        CrosstermKeyCode::PageDown => KeyCode::PageDown,
+        CrosstermKeyCode::Esc => KeyCode::Esc,
+        _ => return None,
+    };
+    Some(KeyEvent {
+        code,
+        ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+        alt: key.modifiers.contains(KeyModifiers::ALT),
+        shift,
+    })
+}
+
+/// Today's date in UTC as `YYYY-MM-DD`, the format of `Meetup::date`.
+fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// The year, month and day `days` after 1970-01-01, by Howard Hinnant's `civil_from_days`.
+///
+/// See: <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::CrosstermBackend;
+    use ratatui::layout::Rect;
+    use ratatui::{Terminal, TerminalOptions, Viewport};
+
+    #[test]
+    fn test_native_terminal_draws_the_site() {
+        // A fixed viewport over a buffer, so no tty is needed
+        let mut output = Vec::new();
+        let mut app = new_app(80, 24);
+        {
+            let backend = CrosstermBackend::new(&mut output);
+            let viewport = Viewport::Fixed(Rect::new(0, 0, 80, 24));
+            let mut terminal =
+                Terminal::with_options(backend, TerminalOptions { viewport }).unwrap();
+            let completed = terminal
+                .draw(|frame| draw(frame, &mut app, &[], 0.0))
+                .unwrap();
+            assert_eq!(completed.area, Rect::new(0, 0, 80, 24));
+        }
+        assert_eq!(app.screen, (80, 24));
+        assert!(String::from_utf8_lossy(&output).contains("Terminal Collective"));
+    }
+
+    #[test]
+    fn test_keys_follow_the_browser_bindings() {
+        let back_tab = CrosstermKeyEvent::new(CrosstermKeyCode::BackTab, KeyModifiers::SHIFT);
+        let key = key_event(back_tab).unwrap();
+        assert_eq!((key.code, key.shift), (KeyCode::Tab, true));
+        assert!(key_event(CrosstermKeyEvent::from(CrosstermKeyCode::Insert)).is_none());
+
+        let mut app = new_app(10, 10);
+        let space = CrosstermKeyEvent::from(CrosstermKeyCode::Char(' '));
+        handle_key(&mut app, key_event(space).unwrap());
+        assert_eq!(app.simulation, crate::Simulation::Paused);
+        let glyph = CrosstermKeyEvent::from(CrosstermKeyCode::Char('a'));
+        handle_key(&mut app, key_event(glyph).unwrap());
+        assert_eq!(app.glyph, 1);
+
+        assert!(is_quit(&CrosstermKeyEvent::from(CrosstermKeyCode::Char(
+            'q'
+        ))));
+        let ctrl_c = CrosstermKeyEvent::new(CrosstermKeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(is_quit(&ctrl_c));
+        assert!(!is_quit(&CrosstermKeyEvent::from(CrosstermKeyCode::Char(
+            'c'
+        ))));
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+}
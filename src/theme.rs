@@ -0,0 +1,112 @@
+//! Color schemes the site can be rendered in.
+
+use ratzilla::ratatui::style::Color;
+
+/// The palette used to render the site.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// The name used to select the theme with `?theme=`.
+    pub name: &'static str,
+    /// The border around the content panels.
+    pub border: Color,
+    /// The background behind the content panels.
+    pub background: Color,
+    /// The text in the content panels.
+    pub text: Color,
+    /// Behind the background grid, `Color::Reset` leaving it to the page.
+    pub page: Color,
+    /// Alive cells of the background grid.
+    pub cell: Color,
+    /// Alive cells of the second color in two color Life.
+    pub cell_b: Color,
+    /// The color dying cells fade into.
+    pub fade: (u8, u8, u8),
+    /// Colors alive cells shift through as they age, from newborn to long-lived.
+    pub age_gradient: [(u8, u8, u8); 3],
+}
+
+/// The green on navy look of the site.
+pub const DEFAULT: Theme = Theme {
+    name: "default",
+    border: Color::Rgb(73, 222, 128),
+    background: Color::Rgb(16, 24, 39),
+    text: Color::Rgb(73, 222, 128),
+    page: Color::Reset,
+    cell: Color::Rgb(100, 100, 100),
+    cell_b: Color::Rgb(140, 110, 70),
+    fade: (0, 0, 0),
+    age_gradient: [(60, 160, 100), (50, 140, 140), (60, 100, 170)],
+};
+
+/// Dark green on white for visitors using a light color scheme.
+pub const LIGHT: Theme = Theme {
+    name: "light",
+    border: Color::Rgb(21, 128, 61),
+    background: Color::Rgb(255, 255, 255),
+    text: Color::Rgb(20, 83, 45),
+    page: Color::Rgb(241, 245, 249),
+    cell: Color::Rgb(203, 213, 225),
+    cell_b: Color::Rgb(230, 214, 190),
+    fade: (241, 245, 249),
+    age_gradient: [(167, 214, 184), (165, 205, 212), (176, 190, 226)],
+};
+
+/// White text on black, well above the WCAG AAA contrast ratio of 7:1.
+pub const HIGH_CONTRAST: Theme = Theme {
+    name: "high-contrast",
+    border: Color::Rgb(255, 255, 0),
+    background: Color::Rgb(0, 0, 0),
+    text: Color::Rgb(255, 255, 255),
+    page: Color::Reset,
+    cell: Color::Rgb(90, 90, 90),
+    cell_b: Color::Rgb(90, 90, 140),
+    fade: (0, 0, 0),
+    age_gradient: [(90, 90, 90), (90, 90, 90), (90, 90, 90)],
+};
+
+/// Shades of gray only.
+pub const MONOCHROME: Theme = Theme {
+    name: "monochrome",
+    border: Color::Rgb(200, 200, 200),
+    background: Color::Rgb(20, 20, 20),
+    text: Color::Rgb(220, 220, 220),
+    page: Color::Reset,
+    cell: Color::Rgb(100, 100, 100),
+    cell_b: Color::Rgb(150, 150, 150),
+    fade: (0, 0, 0),
+    age_gradient: [(110, 110, 110), (90, 90, 90), (70, 70, 70)],
+};
+
+/// The themes in the order they are cycled through.
+pub const THEMES: &[Theme] = &[DEFAULT, LIGHT, HIGH_CONTRAST, MONOCHROME];
+
+impl Theme {
+    /// Returns the index into `THEMES` of the preset called `name`, if any.
+    pub fn index_by_name(name: &str) -> Option<usize> {
+        THEMES.iter().position(|theme| theme.name == name)
+    }
+
+    /// Returns the index into `THEMES` of the theme matching a light or dark color scheme.
+    pub fn index_for_scheme(light: bool) -> usize {
+        let theme = if light { LIGHT } else { DEFAULT };
+        Self::index_by_name(theme.name).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_by_name() {
+        let index = Theme::index_by_name("high-contrast").unwrap();
+        assert_eq!(THEMES[index], HIGH_CONTRAST);
+        assert_eq!(Theme::index_by_name("neon"), None);
+    }
+
+    #[test]
+    fn test_theme_for_scheme() {
+        assert_eq!(THEMES[Theme::index_for_scheme(true)], LIGHT);
+        assert_eq!(THEMES[Theme::index_for_scheme(false)], DEFAULT);
+    }
+}
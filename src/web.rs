@@ -0,0 +1,333 @@
+//! Browser integrations that ratzilla does not provide out of the box.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ratzilla::ratatui::layout::Position;
+use web_sys::js_sys;
+use web_sys::wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+/// The id ratzilla's `DomBackend` gives to the element holding the terminal cells.
+const GRID_ID: &str = "grid";
+
+/// Calls `callback` with the terminal cell under the pointer whenever the page is clicked.
+pub fn on_cell_click<F>(mut callback: F)
+where
+    F: FnMut(Position) + 'static,
+{
+    let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::MouseEvent| {
+        if let Some(position) = cell_position(&event) {
+            callback(position);
+        }
+    });
+    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        let _ =
+            document.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref());
+    }
+    closure.forget();
+}
+
+/// A mouse button pressed, moved or released over the page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellMouse {
+    /// The button was pressed over a cell, `erase` being whether Shift was held and `select`
+    /// whether Alt was
+    Down {
+        position: Position,
+        erase: bool,
+        select: bool,
+    },
+    /// The pointer moved over a cell
+    Move(Position),
+    /// The button was released, wherever the pointer is
+    Up,
+}
+
+/// Calls `callback` whenever a mouse button is pressed or released, and whenever the pointer
+/// moves over the terminal cells.
+pub fn on_cell_mouse<F>(callback: F)
+where
+    F: FnMut(CellMouse) + 'static,
+{
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let callback = Rc::new(RefCell::new(callback));
+    for event_type in ["mousedown", "mousemove", "mouseup"] {
+        let callback = callback.clone();
+        let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::MouseEvent| {
+            let action = match event.type_().as_str() {
+                "mouseup" => Some(CellMouse::Up),
+                "mousedown" => cell_position(&event).map(|position| CellMouse::Down {
+                    position,
+                    erase: event.shift_key(),
+                    select: event.alt_key(),
+                }),
+                _ => cell_position(&event).map(CellMouse::Move),
+            };
+            if let Some(action) = action {
+                (callback.borrow_mut())(action);
+            }
+        });
+        let _ =
+            document.add_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref());
+        closure.forget();
+    }
+}
+
+/// Maps the pointer position of `event` to a cell of the rendered terminal.
+///
+/// The cell size is measured from the first rendered line, as the font size
+/// changes with the viewport width.
+fn cell_position(event: &web_sys::MouseEvent) -> Option<Position> {
+    let grid = web_sys::window()?.document()?.get_element_by_id(GRID_ID)?;
+    let line = grid.first_element_child()?;
+    let cell = line.first_element_child()?;
+    let grid_rect = grid.get_bounding_client_rect();
+    let cell_width = cell.get_bounding_client_rect().width();
+    let cell_height = line.get_bounding_client_rect().height();
+    if cell_width <= 0.0 || cell_height <= 0.0 {
+        return None;
+    }
+    let x = (f64::from(event.client_x()) - grid_rect.left()) / cell_width;
+    let y = (f64::from(event.client_y()) - grid_rect.top()) / cell_height;
+    if x < 0.0 || y < 0.0 {
+        return None;
+    }
+    Some(Position::new(x as u16, y as u16))
+}
+
+/// Calls `callback` with the text pasted into the page, e.g. with Ctrl+V.
+pub fn on_paste<F>(mut callback: F)
+where
+    F: FnMut(String) + 'static,
+{
+    let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::ClipboardEvent| {
+        let text = event
+            .clipboard_data()
+            .and_then(|data| data.get_data("text").ok());
+        if let Some(text) = text {
+            event.prevent_default();
+            callback(text);
+        }
+    });
+    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        let _ =
+            document.add_event_listener_with_callback("paste", closure.as_ref().unchecked_ref());
+    }
+    closure.forget();
+}
+
+/// Calls `callback` when the page is hidden or unloaded, the last reliable moment to save state.
+pub fn on_page_hide<F>(mut callback: F)
+where
+    F: FnMut() + 'static,
+{
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(document) = window.document() else {
+        return;
+    };
+    let closure = Closure::<dyn FnMut(_)>::new({
+        let document = document.clone();
+        move |event: web_sys::Event| {
+            if event.type_() == "pagehide" || document.hidden() {
+                callback();
+            }
+        }
+    });
+    let _ = window.add_event_listener_with_callback("pagehide", closure.as_ref().unchecked_ref());
+    let _ = document
+        .add_event_listener_with_callback("visibilitychange", closure.as_ref().unchecked_ref());
+    closure.forget();
+}
+
+/// Keeps the Tab key from moving the browser focus away from the page, as it is used to
+/// move between links instead.
+pub fn prevent_tab_focus() {
+    let closure = Closure::<dyn FnMut(_)>::new(move |event: web_sys::KeyboardEvent| {
+        if event.key() == "Tab" {
+            event.prevent_default();
+        }
+    });
+    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        let _ =
+            document.add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref());
+    }
+    closure.forget();
+}
+
+/// Returns whether the visitor asked the browser for less motion.
+pub fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok())
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+/// The media query matching visitors who prefer a light color scheme.
+const LIGHT_SCHEME_QUERY: &str = "(prefers-color-scheme: light)";
+
+/// Returns whether the visitor prefers a light color scheme.
+pub fn prefers_light_scheme() -> bool {
+    light_scheme_query().is_some_and(|query| query.matches())
+}
+
+/// Calls `callback` with whether a light color scheme is preferred whenever that changes,
+/// for instance when the operating system switches to its dark mode in the evening.
+pub fn on_color_scheme_change<F>(mut callback: F)
+where
+    F: FnMut(bool) + 'static,
+{
+    let Some(query) = light_scheme_query() else {
+        return;
+    };
+    let closure = Closure::<dyn FnMut()>::new({
+        let query = query.clone();
+        move || callback(query.matches())
+    });
+    let _ = query.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref());
+    closure.forget();
+}
+
+fn light_scheme_query() -> Option<web_sys::MediaQueryList> {
+    web_sys::window()?.match_media(LIGHT_SCHEME_QUERY).ok()?
+}
+
+/// Returns the value stored under `key` in localStorage, if any.
+pub fn storage_get(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok().flatten()
+}
+
+/// Stores `value` under `key` in localStorage, logging a warning if it is full or unavailable.
+pub fn storage_set(key: &str, value: &str) {
+    let Some(storage) = local_storage() else {
+        return;
+    };
+    if let Err(err) = storage.set_item(key, value) {
+        web_sys::console::warn_2(&format!("Failed to store {key}").into(), &err);
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    // Accessing localStorage throws in some private browsing contexts.
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Returns the local date of the visitor formatted as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let date = js_sys::Date::new_0();
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date()
+    )
+}
+
+/// Returns a high resolution timestamp in milliseconds, for measuring durations.
+pub fn now() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+/// Creates a canvas of `width` by `height` pixels which is not attached to the page, with its
+/// 2D drawing context.
+pub fn offscreen_canvas(
+    width: u32,
+    height: u32,
+) -> Option<(
+    web_sys::HtmlCanvasElement,
+    web_sys::CanvasRenderingContext2d,
+)> {
+    let document = web_sys::window()?.document()?;
+    let canvas: web_sys::HtmlCanvasElement =
+        document.create_element("canvas").ok()?.dyn_into().ok()?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context = canvas.get_context("2d").ok()??.dyn_into().ok()?;
+    Some((canvas, context))
+}
+
+/// Downloads the image drawn on `canvas` as a PNG called `filename`, logging a warning if the
+/// browser does not allow it.
+pub fn download_png(canvas: &web_sys::HtmlCanvasElement, filename: &str) {
+    if click_download(canvas, filename).is_none() {
+        warn(&format!("Failed to download {filename}"));
+    }
+}
+
+fn click_download(canvas: &web_sys::HtmlCanvasElement, filename: &str) -> Option<()> {
+    let url = canvas.to_data_url().ok()?;
+    let document = web_sys::window()?.document()?;
+    let link: web_sys::HtmlAnchorElement = document.create_element("a").ok()?.dyn_into().ok()?;
+    link.set_href(&url);
+    link.set_download(filename);
+    link.click();
+    Some(())
+}
+
+/// Writes `text` to the clipboard, then calls `callback` with whether it was written.
+///
+/// The clipboard is only available to secure contexts, and the browser may refuse access.
+pub fn copy_to_clipboard<F>(text: &str, callback: F)
+where
+    F: Fn(bool) + 'static,
+{
+    let Some(window) = web_sys::window() else {
+        return callback(false);
+    };
+    let clipboard = window.navigator().clipboard();
+    if clipboard.is_undefined() {
+        return callback(false);
+    }
+    let callback = Rc::new(callback);
+    let on_written = Closure::<dyn FnMut(_)>::new({
+        let callback = callback.clone();
+        move |_: JsValue| callback(true)
+    });
+    let on_rejected = Closure::<dyn FnMut(_)>::new(move |_: JsValue| callback(false));
+    let _ = clipboard.write_text(text).then2(&on_written, &on_rejected);
+    on_written.forget();
+    on_rejected.forget();
+}
+
+/// Removes the element with the id `id` from the page, if there is one.
+pub fn remove_element(id: &str) {
+    if let Some(element) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id))
+    {
+        element.remove();
+    }
+}
+
+/// Logs `error` in the browser console and replaces the page with a plain paragraph of
+/// `message`, for when the terminal can't be shown at all.
+pub fn show_fatal_error(message: &str, error: &str) {
+    web_sys::console::error_2(&message.into(), &error.into());
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let (Some(body), Ok(paragraph)) = (document.body(), document.create_element("p")) else {
+        return;
+    };
+    paragraph.set_text_content(Some(message));
+    body.set_text_content(None);
+    let _ = body.append_child(&paragraph);
+}
+
+/// Logs `message` as a warning in the browser console.
+pub fn warn(message: &str) {
+    web_sys::console::warn_1(&message.into());
+}
+
+/// Returns the value of the query parameter `name` in the page URL, if present.
+pub fn query_param(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(name)
+}